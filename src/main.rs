@@ -8,9 +8,10 @@ use annotate_snippets::snippet::Snippet;
 
 use clap::{Parser, ValueEnum};
 
+use xgovw_lint::lints::{Expect, Lint};
 use xgovw_lint::reporters::count::Count;
 use xgovw_lint::reporters::{Json, Reporter, Text};
-use xgovw_lint::{default_lints, Linter};
+use xgovw_lint::{default_lints, registry, Linter};
 
 use std::collections::HashMap;
 use std::ffi::OsStr;
@@ -21,11 +22,22 @@ use tokio::fs;
 #[derive(Debug, Parser)]
 struct Opts {
     /// List all available lints.
-    #[clap(exclusive(true), long)]
+    #[clap(
+        long,
+        conflicts_with_all(&["sources", "explain", "lints", "add-lint", "no-default-lints"])
+    )]
     list_lints: bool,
 
+    /// Describe a lint, with examples of documents it accepts and rejects.
+    #[clap(
+        long,
+        value_name = "SLUG",
+        conflicts_with_all(&["sources", "lints", "add-lint", "no-default-lints"])
+    )]
+    explain: Option<String>,
+
     /// Files and/or directories to check.
-    #[clap(required_unless_present("list-lints"))]
+    #[clap(required_unless_present_any(&["list-lints", "explain"]))]
     sources: Vec<PathBuf>,
 
     /// Output format.
@@ -33,12 +45,16 @@ struct Opts {
     format: Format,
 
     /// Do not enable the default lints.
-    #[clap(long, requires_all(&["lints"]))]
+    #[clap(long, requires("enabled"))]
     no_default_lints: bool,
 
     /// Additional lints to enable.
-    #[clap(long, value_delimiter(','))]
+    #[clap(long, value_delimiter(','), group("enabled"))]
     lints: Vec<String>,
+
+    /// Enable a lint of any kind, configured with JSON arguments.
+    #[clap(long, value_name = "SLUG=KIND[=ARGS]", group("enabled"))]
+    add_lint: Vec<String>,
 }
 
 #[derive(ValueEnum, Clone, Debug)]
@@ -68,16 +84,155 @@ impl Reporter for EitherReporter {
     }
 }
 
-fn list_lints() {
+fn lint_to_json(slug: &str, lint: &dyn Lint) -> serde_json::Value {
+    let mut value = serde_json::to_value(lint.metadata()).unwrap();
+    value
+        .as_object_mut()
+        .unwrap()
+        .insert("slug".into(), slug.into());
+    value
+}
+
+fn list_lints(format: &Format) {
+    if let Format::Json = format {
+        let lints: Vec<_> = default_lints()
+            .map(|(slug, lint)| lint_to_json(slug, &*lint))
+            .collect();
+        serde_json::to_writer_pretty(std::io::stdout(), &lints).unwrap();
+        println!();
+        return;
+    }
+
     println!("Available lints:");
 
-    for (slug, _) in default_lints() {
-        println!("\t{}", slug);
+    for (slug, lint) in default_lints() {
+        println!("\t{}: {}", slug, lint.metadata().summary);
+    }
+
+    println!();
+    println!("Available lint kinds (for `--add-lint`):");
+
+    for kind in registry::kinds() {
+        println!("\t{}", kind.name());
     }
 
     println!();
 }
 
+fn find_lint(slug: &str) -> Option<Box<dyn Lint>> {
+    default_lints().find(|(s, _)| *s == slug).map(|(_, l)| l)
+}
+
+fn unknown_lint(slug: &str) -> String {
+    let suggestion = registry::suggest(slug, default_lints().map(|(s, _)| s));
+    let mut msg = format!("no lint with the slug `{}`", slug);
+    msg.push_str(&registry::did_you_mean(&suggestion));
+
+    if registry::find(slug).is_ok() {
+        msg.push_str(&format!(
+            " (`{}` is a lint kind, try `--add-lint <SLUG>={}`)",
+            slug, slug
+        ));
+    }
+
+    msg
+}
+
+/// Parses a `SLUG=KIND[=ARGS]` lint definition, as given to `--add-lint`.
+fn parse_lint(def: &str) -> Result<(&str, Box<dyn Lint>), String> {
+    let mut parts = def.splitn(3, '=');
+
+    let slug = parts.next().unwrap_or_default();
+    let kind = match parts.next() {
+        Some(k) if !slug.is_empty() => k,
+        _ => return Err(format!("expected `SLUG=KIND[=ARGS]`, got `{}`", def)),
+    };
+
+    let args = match parts.next() {
+        Some(a) => serde_json::from_str(a)
+            .map_err(|e| format!("arguments for `{}` are not valid JSON: {}", slug, e))?,
+        None => serde_json::Value::Null,
+    };
+
+    let lint = registry::build_lint(kind, args).map_err(|e| e.to_string())?;
+
+    Ok((slug, lint))
+}
+
+async fn explain(slug: &str, format: &Format) -> Result<(), String> {
+    let lint = find_lint(slug).ok_or_else(|| unknown_lint(slug))?;
+
+    if let Format::Json = format {
+        serde_json::to_writer_pretty(std::io::stdout(), &lint_to_json(slug, &*lint)).unwrap();
+        println!();
+        return Ok(());
+    }
+
+    let metadata = lint.metadata();
+
+    println!("{}: {}", slug, metadata.summary);
+    println!();
+
+    if !metadata.rationale.is_empty() {
+        println!("{}", metadata.rationale);
+        println!();
+    }
+
+    if !metadata.parameters.is_empty() {
+        println!("Parameters:");
+        for parameter in &metadata.parameters {
+            println!("\t{} = {}", parameter.name, parameter.value);
+        }
+        println!();
+    }
+
+    for example in &metadata.examples {
+        let title = match example.expect {
+            Expect::Pass => "Passing example",
+            Expect::Fail => "Failing example",
+        };
+
+        match &example.origin {
+            Some(origin) => println!("{} (`{}`):", title, origin),
+            None => println!("{}:", title),
+        }
+        println!();
+
+        for line in example.source.lines() {
+            println!("\t{}", line);
+        }
+        println!();
+
+        for (path, source) in &example.files {
+            println!("With `{}`:", path);
+            println!();
+            for line in source.lines() {
+                println!("\t{}", line);
+            }
+            println!();
+        }
+
+        if example.expect == Expect::Pass {
+            continue;
+        }
+
+        // Lint the example for real, so the output always matches what
+        // `xgovw` reports.
+        let reporter = example
+            .lint(Text::<String>::default(), slug, find_lint(slug).unwrap())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        println!("Produces:");
+        println!();
+        for line in reporter.into_inner().lines() {
+            println!("\t{}", line);
+        }
+    }
+
+    Ok(())
+}
+
 async fn collect_sources(sources: Vec<PathBuf>) -> Result<Vec<PathBuf>, std::io::Error> {
     let mut output = Vec::with_capacity(sources.len());
 
@@ -111,12 +266,27 @@ async fn collect_sources(sources: Vec<PathBuf>) -> Result<Vec<PathBuf>, std::io:
     Ok(output)
 }
 
+fn fail<E>(msg: E) -> !
+where
+    E: std::fmt::Display,
+{
+    eprintln!("error: {}", msg);
+    std::process::exit(1);
+}
+
 #[tokio::main]
 async fn run() -> Result<(), usize> {
     let opts = Opts::parse();
 
     if opts.list_lints {
-        list_lints();
+        list_lints(&opts.format);
+        return Ok(());
+    }
+
+    if let Some(slug) = &opts.explain {
+        if let Err(e) = explain(slug, &opts.format).await {
+            fail(e);
+        }
         return Ok(());
     }
 
@@ -140,7 +310,17 @@ async fn run() -> Result<(), usize> {
     if !opts.lints.is_empty() {
         let mut lints: HashMap<_, _> = default_lints().collect();
         for slug in &opts.lints {
-            linter = linter.add_lint(slug, lints.remove(slug.as_str()).unwrap());
+            match lints.remove(slug.as_str()) {
+                Some(lint) => linter = linter.add_lint(slug, lint).unwrap_or_else(|e| fail(e)),
+                None => fail(unknown_lint(slug)),
+            }
+        }
+    }
+
+    for def in &opts.add_lint {
+        match parse_lint(def) {
+            Ok((slug, lint)) => linter = linter.add_lint(slug, lint).unwrap_or_else(|e| fail(e)),
+            Err(e) => fail(e),
         }
     }
 
@@ -148,7 +328,7 @@ async fn run() -> Result<(), usize> {
         linter = linter.check_file(source);
     }
 
-    let reporter = linter.run().await.unwrap();
+    let reporter = linter.run().await.unwrap_or_else(|e| fail(e));
 
     let n_errors = reporter.counts().error;
 
@@ -8,7 +8,7 @@ use xgovw_lint::fetch::Fetch;
 use xgovw_lint::reporters::json::Json;
 use xgovw_lint::Linter;
 
-use js_sys::JsString;
+use js_sys::{JsString, Reflect, Uint8Array};
 
 use std::fmt;
 use std::future::Future;
@@ -32,6 +32,21 @@ impl std::error::Error for Error {}
 extern "C" {
     #[wasm_bindgen(catch, js_name = readFile)]
     async fn read_file(path: &JsString, encoding: &JsString) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(catch, js_name = readFile)]
+    async fn read_file_bytes(path: &JsString) -> Result<JsValue, JsValue>;
+}
+
+/// Keeps `ENOENT` distinguishable, so lints can tell missing files apart from
+/// other failures.
+fn io_error(e: JsValue) -> std::io::Error {
+    let kind = match Reflect::get(&e, &JsValue::from_str("code")) {
+        Ok(code) if code.as_string().as_deref() == Some("ENOENT") => std::io::ErrorKind::NotFound,
+        _ => std::io::ErrorKind::Other,
+    };
+
+    let txt = format!("{:?}", e);
+    std::io::Error::new(kind, Error(txt))
 }
 
 struct NodeFetch;
@@ -51,10 +66,26 @@ impl Fetch for NodeFetch {
 
             match read_file(&path, &encoding).await {
                 Ok(o) => Ok(o.as_string().unwrap()),
-                Err(e) => {
-                    let txt = format!("{:?}", e);
-                    Err(std::io::Error::new(std::io::ErrorKind::Other, Error(txt)))
-                }
+                Err(e) => Err(io_error(e)),
+            }
+        };
+
+        Box::pin(fut)
+    }
+
+    fn fetch_bytes(
+        &self,
+        path: PathBuf,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, std::io::Error>>>> {
+        let fut = async move {
+            let path = match path.to_str() {
+                Some(p) => JsString::from(p),
+                None => return Err(std::io::ErrorKind::InvalidInput.into()),
+            };
+
+            match read_file_bytes(&path).await {
+                Ok(o) => Ok(Uint8Array::new(&o).to_vec()),
+                Err(e) => Err(io_error(e)),
             }
         };
 
@@ -7,6 +7,7 @@
 #[cfg(feature = "tokio")]
 pub mod tokio;
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
@@ -14,6 +15,16 @@ use std::pin::Pin;
 pub trait Fetch {
     fn fetch(&self, path: PathBuf)
         -> Pin<Box<dyn Future<Output = Result<String, std::io::Error>>>>;
+
+    /// Read a file that might not be text (eg. an image.) The default only
+    /// supports files that are valid UTF-8.
+    fn fetch_bytes(
+        &self,
+        path: PathBuf,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, std::io::Error>>>> {
+        let fut = self.fetch(path);
+        Box::pin(async { fut.await.map(String::into_bytes) })
+    }
 }
 
 #[derive(Debug, Default)]
@@ -29,6 +40,34 @@ impl Fetch for Null {
     }
 }
 
+/// Serves files from memory instead of the file system.
+#[derive(Debug, Default)]
+pub struct Memory(pub HashMap<PathBuf, Vec<u8>>);
+
+impl Fetch for Memory {
+    fn fetch(
+        &self,
+        path: PathBuf,
+    ) -> Pin<Box<dyn Future<Output = Result<String, std::io::Error>>>> {
+        let fut = self.fetch_bytes(path);
+        Box::pin(async {
+            String::from_utf8(fut.await?)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        })
+    }
+
+    fn fetch_bytes(
+        &self,
+        path: PathBuf,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, std::io::Error>>>> {
+        let result = match self.0.get(&path) {
+            Some(b) => Ok(b.clone()),
+            None => Err(std::io::ErrorKind::NotFound.into()),
+        };
+        Box::pin(async { result })
+    }
+}
+
 #[cfg(feature = "tokio")]
 pub use self::tokio::Tokio as DefaultFetch;
 
@@ -15,4 +15,12 @@ impl Fetch for Tokio {
         let fut = async { tokio::fs::read_to_string(path).await };
         Box::pin(fut)
     }
+
+    fn fetch_bytes(
+        &self,
+        path: PathBuf,
+    ) -> Pin<Box<dyn Future<Output = Result<Vec<u8>, std::io::Error>>>> {
+        let fut = async { tokio::fs::read(path).await };
+        Box::pin(fut)
+    }
 }
@@ -5,9 +5,12 @@
  */
 
 pub mod fetch;
+pub mod line_endings;
 pub mod lints;
 pub mod preamble;
+pub mod registry;
 pub mod reporters;
+pub mod span;
 pub mod tree;
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
@@ -16,6 +19,7 @@ use comrak::arena_tree::Node;
 use comrak::nodes::Ast;
 use comrak::{Arena, ComrakExtensionOptions, ComrakOptions};
 
+use crate::line_endings::LineEndings;
 use crate::lints::{Context, Error as LintError, FetchContext, InnerContext, Lint, LintExt as _};
 use crate::preamble::Preamble;
 use crate::reporters::Reporter;
@@ -24,9 +28,12 @@ use educe::Educe;
 
 use snafu::{ensure, ResultExt, Snafu};
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map::{self, HashMap};
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Once;
 
 #[derive(Snafu, Debug)]
 #[non_exhaustive]
@@ -44,16 +51,91 @@ pub enum Error {
         lint: String,
         origin: Option<PathBuf>,
     },
+    #[snafu(display("a lint with the slug `{}` is already enabled", slug))]
+    DuplicateLint { slug: String },
+    #[snafu(display("no lint with the slug `{}` is enabled", slug))]
+    UnknownLint { slug: String },
+    #[snafu(display("no lints are enabled"))]
+    NoLints,
+    #[snafu(display("no sources were given to check"))]
+    NoSources,
+    #[snafu(display(
+        "proposal `{}` was not requested with `fetch` in the lint's `find_resources`",
+        path.display()
+    ))]
+    XGovNotFetched { path: PathBuf },
+    #[snafu(display(
+        "resource `{}` was not requested with `fetch_resource` in the lint's `find_resources`",
+        path.display()
+    ))]
+    ResourceNotFetched { path: PathBuf },
+    #[snafu(display("cannot read `{}` relative to a source without an origin", path.display()))]
+    XGovWithoutOrigin { path: PathBuf },
 }
 
+/// The sections of an application, in order, with the slug of the default lint
+/// that checks the section isn't empty.
+const SECTIONS: &[(&str, &str)] = &[
+    ("Introduction", "markdown-len-introduction"),
+    ("Social Profiles", "markdown-len-social-profiles"),
+    ("Relevant Experience", "markdown-len-relevant-experience"),
+    ("Project Affiliations", "markdown-len-project-affiliations"),
+    (
+        "Additional Information",
+        "markdown-len-additional-information",
+    ),
+];
+
+/// The example application that applicants start from.
+pub const TEMPLATE: &str = include_str!("../template.md");
+
 pub fn default_lints() -> impl Iterator<Item = (&'static str, Box<dyn Lint>)> {
     //use lints::preamble::regex;
     use lints::{markdown, preamble};
 
+    let sections: Vec<_> = SECTIONS.iter().map(|(name, _)| *name).collect();
+
+    let lengths = SECTIONS.iter().map(|(name, slug)| {
+        let lint = markdown::SectionLength {
+            section: Some(*name),
+            min_words: Some(1),
+            max_words: None,
+            min_chars: None,
+            max_chars: None,
+        };
+        (*slug, lint.boxed())
+    });
+
     [
         //
         // File
         //
+        ("markdown-line-endings", markdown::LineEndings.boxed()),
+        ("markdown-final-newline", markdown::FinalNewline.boxed()),
+        (
+            "markdown-trailing-whitespace",
+            markdown::TrailingWhitespace.boxed(),
+        ),
+        ("markdown-tabs", markdown::Tabs.boxed()),
+        ("markdown-blank-lines", markdown::BlankLines.boxed()),
+        ("markdown-secrets", markdown::Secrets.boxed()),
+        (
+            "markdown-privacy",
+            markdown::Privacy {
+                email: "email",
+                sections: vec!["Social Profiles"],
+                allow: vec![],
+            }
+            .boxed(),
+        ),
+        (
+            "markdown-confusables",
+            markdown::Confusables {
+                names: vec!["author"],
+                known: vec![],
+            }
+            .boxed(),
+        ),
         (
             "preamble-file-name",
             preamble::FileName {
@@ -68,25 +150,15 @@ pub fn default_lints() -> impl Iterator<Item = (&'static str, Box<dyn Lint>)> {
         //
         (
             "preamble-req",
-            preamble::Required(&[
-                "id",
-                "author",
-                "email",
-                "address",
-                "status",
-            ])
-            .boxed(),
+            preamble::Required(vec!["id", "author", "email", "address", "status"]).boxed(),
+        ),
+        (
+            "preamble-allowed",
+            preamble::Allowed(vec!["id", "author", "email", "address", "status"]).boxed(),
         ),
         (
             "preamble-order",
-            preamble::Order(&[
-                "id",
-                "author",
-                "email",
-                "address",
-                "status",
-            ])
-            .boxed(),
+            preamble::Order(vec!["id", "author", "email", "address", "status"]).boxed(),
         ),
         ("preamble-no-dup", preamble::NoDuplicates.boxed()),
         ("preamble-trim", preamble::Trim.boxed()),
@@ -101,46 +173,89 @@ pub fn default_lints() -> impl Iterator<Item = (&'static str, Box<dyn Lint>)> {
             .boxed(),
         ),
         ("preamble-author", preamble::Author("author").boxed()),
-        ("preamble-email", preamble::Email("email").boxed()),
-        ("preamble-list-author", preamble::List("author").boxed()),
+        (
+            "preamble-email",
+            preamble::Email {
+                name: "email",
+                reject_disposable: false,
+            }
+            .boxed(),
+        ),
         (
             "markdown-order-section",
-            markdown::SectionOrder(&[
-                "Introduction",
-                "Social Profiles",
-                "Relevant Experience",
-                "Project Affiliations",
-                "Additional Information",
-            ])
+            markdown::SectionOrder(sections.clone()).boxed(),
+        ),
+        (
+            "markdown-headings",
+            markdown::Headings::<&str> { sections: vec![] }.boxed(),
+        ),
+        (
+            "markdown-social-profiles",
+            markdown::SocialProfiles {
+                section: "Social Profiles",
+                author: "author",
+            }
             .boxed(),
         ),
+        (
+            "markdown-tables",
+            markdown::Tables::<&str> { columns: vec![] }.boxed(),
+        ),
         (
             "markdown-required-section",
-            markdown::SectionRequired(&[
-                "Introduction",
-                "Social Profiles",
-                "Relevant Experience",
-                "Project Affiliations",
-                "Additional Information",
-            ])
+            markdown::SectionRequired(sections).boxed(),
+        ),
+    ]
+    .into_iter()
+    .chain(lengths)
+    .chain([
+        (
+            "markdown-external-links",
+            markdown::ExternalLinks {
+                schemes: vec!["https", "mailto"],
+                allow: vec![],
+                deny: vec![
+                    "bit.ly",
+                    "buff.ly",
+                    "cutt.ly",
+                    "goo.gl",
+                    "is.gd",
+                    "ow.ly",
+                    "rebrand.ly",
+                    "shorturl.at",
+                    "t.co",
+                    "tinyurl.com",
+                ],
+            }
             .boxed(),
         ),
+        ("markdown-link-text", markdown::LinkText.boxed()),
+        (
+            "markdown-html",
+            markdown::Html {
+                tags: vec![
+                    "a", "b", "br", "code", "del", "details", "em", "i", "img", "kbd", "li", "ol",
+                    "p", "pre", "strong", "sub", "summary", "sup", "ul",
+                ],
+                attributes: vec![
+                    "alt", "height", "href", "id", "name", "src", "title", "width",
+                ],
+            }
+            .boxed(),
+        ),
+        (
+            "markdown-boilerplate",
+            markdown::Boilerplate::new(TEMPLATE, vec!["author", "email", "address"], None).boxed(),
+        ),
         (
             "preamble-enum-status",
             preamble::OneOf {
                 name: "status",
-                values: &[
-                    "Draft",
-                    "Final",
-                    "Candidate",
-                    "Elected",
-                    "Not Elected",
-                ],
+                values: vec!["Draft", "Final", "Candidate", "Elected", "Not Elected"],
             }
             .boxed(),
         ),
-    ]
-    .into_iter()
+    ])
 }
 
 #[derive(Debug)]
@@ -177,6 +292,20 @@ impl<'a> Source<'a> {
             Self::String { src, .. } => Ok((*src).to_owned()),
         }
     }
+
+    async fn read(
+        &self,
+        fetch: &dyn fetch::Fetch,
+        normalize: bool,
+    ) -> Result<(String, LineEndings), Error> {
+        let content = self.fetch(fetch).await?;
+
+        if normalize {
+            Ok(line_endings::normalize(content))
+        } else {
+            Ok((content, LineEndings::default()))
+        }
+    }
 }
 
 #[derive(Educe)]
@@ -185,6 +314,7 @@ impl<'a> Source<'a> {
 pub struct Linter<'a, R> {
     lints: HashMap<&'a str, Box<dyn Lint>>,
     sources: Vec<Source<'a>>,
+    normalize: bool,
 
     #[educe(Debug(ignore))]
     reporter: R,
@@ -207,28 +337,27 @@ impl<'a, R> Linter<'a, R> {
         Self {
             reporter,
             sources: Default::default(),
+            normalize: true,
             lints: default_lints().collect(),
             fetch: Box::new(fetch::DefaultFetch::default()),
         }
     }
 
-    pub fn add_lint<T>(mut self, slug: &'a str, lint: T) -> Self
+    pub fn add_lint<T>(mut self, slug: &'a str, lint: T) -> Result<Self, Error>
     where
         T: 'static + Lint,
     {
-        if self.lints.insert(slug, lint.boxed()).is_some() {
-            panic!("duplicate slug: {}", slug);
-        }
+        match self.lints.entry(slug) {
+            hash_map::Entry::Occupied(_) => return DuplicateLintSnafu { slug }.fail(),
+            hash_map::Entry::Vacant(v) => v.insert(lint.boxed()),
+        };
 
-        self
+        Ok(self)
     }
 
-    pub fn remove_lint(mut self, slug: &str) -> Self {
-        if self.lints.remove(slug).is_none() {
-            panic!("no lint with the slug: {}", slug);
-        }
-
-        self
+    pub fn remove_lint(mut self, slug: &str) -> Result<Self, Error> {
+        ensure!(self.lints.remove(slug).is_some(), UnknownLintSnafu { slug });
+        Ok(self)
     }
 
     pub fn clear_lints(mut self) -> Self {
@@ -236,6 +365,14 @@ impl<'a, R> Linter<'a, R> {
         self
     }
 
+    /// Strip byte order marks and convert `\r\n` line endings to `\n` before
+    /// parsing (enabled by default.) Lints can still see what was changed
+    /// through [`Context::line_endings`](lints::Context::line_endings).
+    pub fn normalize_line_endings(mut self, normalize: bool) -> Self {
+        self.normalize = normalize;
+        self
+    }
+
     pub fn set_fetch<F>(mut self, fetch: F) -> Self
     where
         F: 'static + fetch::Fetch,
@@ -260,29 +397,31 @@ where
     }
 
     pub async fn run(self) -> Result<R, Error> {
-        if self.lints.is_empty() {
-            panic!("no lints activated");
-        }
-
-        if self.sources.is_empty() {
-            panic!("no sources given");
-        }
+        ensure!(!self.lints.is_empty(), NoLintsSnafu);
+        ensure!(!self.sources.is_empty(), NoSourcesSnafu);
 
         let mut to_check = Vec::with_capacity(self.sources.len());
         let mut fetched_xgovs = HashMap::new();
+        let mut fetched_resources = HashMap::new();
 
         for source in self.sources {
             let source_origin = source.origin().map(Path::to_path_buf);
-            let source_content = source.fetch(&*self.fetch).await?;
+            let (source_content, line_endings) = source.read(&*self.fetch, self.normalize).await?;
 
-            to_check.push((source_origin, source_content));
+            to_check.push((source_origin, source_content, line_endings));
 
-            let (source_origin, source_content) = to_check.last().unwrap();
+            let (source_origin, source_content, line_endings) = to_check.last().unwrap();
             let display_origin = source_origin.as_deref().map(Path::to_string_lossy);
             let display_origin = display_origin.as_deref();
 
             let arena = Arena::new();
-            let inner = match process(&reporters::Null, &arena, display_origin, source_content)? {
+            let inner = match process(
+                &reporters::Null,
+                &arena,
+                display_origin,
+                source_content,
+                line_endings,
+            )? {
                 Some(i) => i,
                 None => continue,
             };
@@ -292,32 +431,37 @@ where
                     body: inner.body,
                     preamble: &inner.preamble,
                     xgovs: Default::default(),
+                    resources: Default::default(),
                 };
 
-                lint.find_resources(&context).with_context(|_| LintSnafu {
+                isolate(&self.reporter, slug, display_origin, source_content, || {
+                    lint.find_resources(&context)
+                })
+                .with_context(|_| LintSnafu {
                     origin: source_origin.clone(),
                 })?;
 
                 let xgovs = context.xgovs.into_inner();
+                let resources = context.resources.into_inner();
 
                 // For now, string sources shouldn't be allowed to fetch external
                 // resources. The origin field isn't guaranteed to be a file/URL,
                 // and even if it was, we wouldn't know which of those to interpret
                 // it as.
                 ensure!(
-                    xgovs.is_empty() || !source.is_string(),
+                    (xgovs.is_empty() && resources.is_empty()) || !source.is_string(),
                     SliceFetchedSnafu {
                         lint: *slug,
                         origin: source_origin.clone(),
                     }
                 );
 
-                for xgov in xgovs.into_iter() {
-                    let root = match source {
-                        Source::File(p) => p.parent().unwrap_or_else(|| Path::new(".")),
-                        _ => unreachable!(),
-                    };
+                let root = match source {
+                    Source::File(p) => p.parent().unwrap_or_else(|| Path::new(".")),
+                    _ => Path::new("."),
+                };
 
+                for xgov in xgovs.into_iter() {
                     let path = root.join(xgov);
 
                     let entry = match fetched_xgovs.entry(path) {
@@ -325,7 +469,27 @@ where
                         hash_map::Entry::Vacant(v) => v,
                     };
 
-                    let content = Source::File(entry.key()).fetch(&*self.fetch).await;
+                    let content = Source::File(entry.key())
+                        .read(&*self.fetch, self.normalize)
+                        .await;
+                    entry.insert(content);
+                }
+
+                for resource in resources.into_iter() {
+                    let path = root.join(resource);
+
+                    let entry = match fetched_resources.entry(path) {
+                        hash_map::Entry::Occupied(_) => continue,
+                        hash_map::Entry::Vacant(v) => v,
+                    };
+
+                    let content = self
+                        .fetch
+                        .fetch_bytes(entry.key().clone())
+                        .await
+                        .with_context(|_| IoSnafu {
+                            path: entry.key().clone(),
+                        });
                     entry.insert(content);
                 }
             }
@@ -335,7 +499,7 @@ where
         let mut parsed_xgovs = HashMap::new();
 
         for (origin, result) in &fetched_xgovs {
-            let source = match result {
+            let (source, line_endings) = match result {
                 Ok(o) => o,
                 Err(e) => {
                     parsed_xgovs.insert(origin.as_path(), Err(e));
@@ -343,7 +507,8 @@ where
                 }
             };
 
-            let inner = match process(&self.reporter, &resources_arena, None, source)? {
+            let inner = match process(&self.reporter, &resources_arena, None, source, line_endings)?
+            {
                 Some(s) => s,
                 None => return Ok(self.reporter),
             };
@@ -353,12 +518,13 @@ where
         let mut lints: Vec<_> = self.lints.iter().collect();
         lints.sort_by_key(|l| l.0);
 
-        for (origin, source) in &to_check {
+        for (origin, source, line_endings) in &to_check {
             let display_origin = origin.as_ref().map(|p| p.to_string_lossy().into_owned());
             let display_origin = display_origin.as_deref();
 
             let arena = Arena::new();
-            let inner = match process(&self.reporter, &arena, display_origin, source)? {
+            let inner = match process(&self.reporter, &arena, display_origin, source, line_endings)?
+            {
                 Some(i) => i,
                 None => continue,
             };
@@ -367,10 +533,14 @@ where
                 inner,
                 reporter: &self.reporter,
                 xgovs: &parsed_xgovs,
+                resources: &fetched_resources,
             };
 
             for (slug, lint) in &lints {
-                lint.lint(slug, &context).with_context(|_| LintSnafu {
+                isolate(&self.reporter, slug, display_origin, source, || {
+                    lint.lint(slug, &context)
+                })
+                .with_context(|_| LintSnafu {
                     origin: origin.clone(),
                 })?;
             }
@@ -380,11 +550,103 @@ where
     }
 }
 
+thread_local! {
+    /// Whether a panic on this thread will be caught and reported by `isolate`.
+    static ISOLATING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Keeps the panic hook from printing panics that `isolate` reports as
+/// diagnostics. Other panics still go to the hook that was installed before.
+fn quiet_isolated_panics() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !ISOLATING.with(Cell::get) {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Runs a single lint invocation, turning its errors and panics into an
+/// "internal lint error" diagnostic so the remaining lints and sources are
+/// still checked. Only failures of the reporter itself are returned.
+fn isolate<F>(
+    reporter: &dyn Reporter,
+    slug: &str,
+    origin: Option<&str>,
+    source: &str,
+    f: F,
+) -> Result<(), LintError>
+where
+    F: FnOnce() -> Result<(), LintError>,
+{
+    quiet_isolated_panics();
+
+    let isolating = ISOLATING.with(|i| i.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATING.with(|i| i.set(isolating));
+
+    let message = match result {
+        Ok(Ok(())) => return Ok(()),
+        Ok(Err(e @ LintError::ReportFailed { .. })) => return Err(e),
+        Ok(Err(e)) => e.to_string(),
+        Err(payload) => {
+            if let Some(s) = payload.downcast_ref::<&str>() {
+                format!("panicked: {}", s)
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                format!("panicked: {}", s)
+            } else {
+                "panicked".to_string()
+            }
+        }
+    };
+
+    let label = format!("internal lint error: {}", message);
+
+    reporter.report(Snippet {
+        title: Some(Annotation {
+            id: Some(slug),
+            label: Some(&label),
+            annotation_type: AnnotationType::Error,
+        }),
+        slices: vec![Slice {
+            fold: false,
+            line_start: 1,
+            origin,
+            source: source.lines().next().unwrap_or_default(),
+            annotations: vec![],
+        }],
+        footer: vec![Annotation {
+            id: None,
+            label: Some("this is a problem with the lint or its configuration, not the document"),
+            annotation_type: AnnotationType::Note,
+        }],
+        ..Default::default()
+    })?;
+
+    Ok(())
+}
+
+/// The markdown extensions enabled when parsing a body.
+pub(crate) fn comrak_options() -> ComrakOptions {
+    ComrakOptions {
+        extension: ComrakExtensionOptions {
+            table: true,
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
 fn process<'r, 'a>(
     reporter: &'r dyn Reporter,
     arena: &'a Arena<Node<'a, RefCell<Ast>>>,
     origin: Option<&'a str>,
     source: &'a str,
+    line_endings: &'a LineEndings,
 ) -> Result<Option<InnerContext<'a>>, Error> {
     let (preamble_source, body_source) = match Preamble::split(source) {
         Ok(v) => v,
@@ -456,36 +718,388 @@ fn process<'r, 'a>(
         }
     };
 
-    let options = ComrakOptions {
-        extension: ComrakExtensionOptions {
-            table: true,
-            ..Default::default()
-        },
-        ..Default::default()
-    };
-
     let mut preamble_lines: u32 = preamble_source.matches('\n').count().try_into().unwrap();
     preamble_lines += 3;
 
-    let body = comrak::parse_document(arena, body_source, &options);
+    let body = comrak::parse_document(arena, body_source, &comrak_options());
 
     for node in body.descendants() {
         let mut data = node.data.borrow_mut();
-        if data.start_line == 0 {
-            if let Some(parent) = node.parent() {
-                // XXX: This doesn't actually work.
-                data.start_line = parent.data.borrow().start_line;
-            }
-        } else {
+        if data.start_line != 0 {
             data.start_line += preamble_lines;
         }
     }
 
+    let spans = span::compute(source, preamble_lines as usize + 1, body);
+
+    // comrak doesn't record lines for inline nodes, so fill them in.
+    for node in body.descendants() {
+        let mut data = node.data.borrow_mut();
+        if data.start_line != 0 {
+            continue;
+        }
+
+        if let Some(span) = spans.get(&data) {
+            data.start_line = span.start().line().try_into().unwrap();
+        } else if let Some(parent) = node.parent() {
+            data.start_line = parent.data.borrow().start_line;
+        }
+    }
+
     Ok(Some(InnerContext {
         body,
         source,
         body_source,
         preamble,
         origin,
+        line_endings,
+        spans: Rc::new(spans),
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use crate::lints::Expect;
+    use crate::reporters::count::Count;
+    use crate::reporters::Text;
+
+    use super::*;
+
+    #[test]
+    fn add_lint_duplicate() {
+        let result =
+            Linter::new(Text::<String>::default()).add_lint("preamble-trim", lints::preamble::Trim);
+        assert_matches!(result, Err(Error::DuplicateLint { slug }) if slug == "preamble-trim");
+    }
+
+    #[test]
+    fn remove_lint_unknown() {
+        let result = Linter::new(Text::<String>::default()).remove_lint("not-a-lint");
+        assert_matches!(result, Err(Error::UnknownLint { slug }) if slug == "not-a-lint");
+    }
+
+    #[tokio::test]
+    async fn run_without_lints() {
+        let result = Linter::new(Text::<String>::default())
+            .clear_lints()
+            .check_slice(None, "---\nid: 1\n---\n")
+            .run()
+            .await;
+        assert_matches!(result, Err(Error::NoLints));
+    }
+
+    #[tokio::test]
+    async fn run_without_sources() {
+        let result = Linter::new(Text::<String>::default()).run().await;
+        assert_matches!(result, Err(Error::NoSources));
+    }
+
+    #[tokio::test]
+    async fn lint_error_is_isolated() {
+        let reporter = Linter::new(Count::new(Text::<String>::default()))
+            .clear_lints()
+            .add_lint(
+                "markdown-bad-regex",
+                lints::markdown::Regex {
+                    mode: lints::markdown::regex::Mode::Excludes,
+                    pattern: "(",
+                    message: "unreachable",
+                },
+            )
+            .unwrap()
+            .add_lint("preamble-trim", lints::preamble::Trim)
+            .unwrap()
+            .check_slice(None, "---\nid:1\n---\nHello\n")
+            .check_slice(None, "---\nid:2\n---\nWorld\n")
+            .run()
+            .await
+            .unwrap();
+
+        let counts = reporter.counts();
+        let reported = reporter.into_inner().into_inner();
+
+        assert_eq!(counts.error, 4, "{}", reported);
+        assert_eq!(reported.matches("internal lint error").count(), 2);
+    }
+
+    #[tokio::test]
+    async fn lint_panic_is_isolated() {
+        let reporter = Linter::new(Count::new(Text::<String>::default()))
+            .clear_lints()
+            .add_lint(
+                "preamble-file-name",
+                lints::preamble::FileName {
+                    name: "id",
+                    prefix: "xgov-",
+                    suffix: ".md",
+                },
+            )
+            .unwrap()
+            .check_slice(Some("xgov-1.md"), "---\nid: 99999999999\n---\nHello\n")
+            .run()
+            .await
+            .unwrap();
+
+        let counts = reporter.counts();
+        let reported = reporter.into_inner().into_inner();
+
+        assert_eq!(counts.error, 1, "{}", reported);
+        assert!(
+            reported.contains("internal lint error: panicked"),
+            "{}",
+            reported
+        );
+    }
+
+    #[test]
+    fn not_fetched_messages_differ() {
+        let path = PathBuf::from("xgov-1.md");
+        let xgov = Error::XGovNotFetched { path: path.clone() }.to_string();
+        let resource = Error::ResourceNotFetched { path }.to_string();
+
+        assert!(xgov.starts_with("proposal `xgov-1.md`"), "{}", xgov);
+        assert!(resource.starts_with("resource `xgov-1.md`"), "{}", resource);
+    }
+
+    #[tokio::test]
+    async fn empty_list_items() {
+        for (source, empty) in [
+            ("---\nauthor: a,\n---\n", 1),
+            ("---\nauthor: ,,,\n---\n", 4),
+        ] {
+            let reporter = Linter::new(Count::new(Text::<String>::default()))
+                .clear_lints()
+                .add_lint("preamble-list", lints::preamble::List("author"))
+                .unwrap()
+                .add_lint("preamble-author", lints::preamble::Author("author"))
+                .unwrap()
+                .check_slice(None, source)
+                .run()
+                .await
+                .unwrap();
+
+            let reported = reporter.into_inner().into_inner();
+
+            assert!(!reported.contains("internal lint error"), "{}", reported);
+            for slug in ["preamble-list", "preamble-author"] {
+                let label = format!(
+                    "[{}]: preamble header `author` cannot have empty items",
+                    slug
+                );
+                assert_eq!(reported.matches(&label).count(), empty, "{}", reported);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn crlf_after_non_ascii() {
+        let reporter = Linter::new(Count::new(Text::<String>::default()))
+            .clear_lints()
+            .add_lint("markdown-line-endings", lints::markdown::LineEndings)
+            .unwrap()
+            .check_slice(None, "---\nid: 1\n---\n\nCafé crème\r\nText\n")
+            .run()
+            .await
+            .unwrap();
+
+        let counts = reporter.counts();
+        let reported = reporter.into_inner().into_inner();
+
+        assert_eq!(counts.error, 1, "{}", reported);
+        assert!(!reported.contains("internal lint error"), "{}", reported);
+        assert!(
+            reported.contains("Café crème\n  |            carriage return (CR) here"),
+            "{}",
+            reported
+        );
+    }
+
+    /// Serves the given files from memory.
+    fn files<const N: usize>(files: [(&str, &[u8]); N]) -> fetch::Memory {
+        fetch::Memory(
+            files
+                .into_iter()
+                .map(|(path, bytes)| (PathBuf::from(path), bytes.to_vec()))
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn link_targets_are_fetched() {
+        let files = files([
+            (
+                "apps/xgov-1.md",
+                b"---\nid: 1\n---\n\n\
+                [ok](other.md#intro) [bad fragment](other.md#outro) \
+                [missing](logo.png) [escapes](../../secret.md)\n" as &[u8],
+            ),
+            ("apps/other.md", b"# Intro\n"),
+        ]);
+
+        let reporter = Linter::new(Count::new(Text::<String>::default()))
+            .clear_lints()
+            .add_lint(
+                "markdown-link-targets",
+                lints::markdown::LinkTargets { depth: 1 },
+            )
+            .unwrap()
+            .set_fetch(files)
+            .check_file(Path::new("apps/xgov-1.md"))
+            .run()
+            .await
+            .unwrap();
+
+        let counts = reporter.counts();
+        let reported = reporter.into_inner().into_inner();
+
+        assert_eq!(counts.error, 3, "{}", reported);
+        assert!(
+            reported.contains("no heading for `#outro` in `other.md`"),
+            "{}",
+            reported
+        );
+        assert!(reported.contains("did you mean `#intro`?"), "{}", reported);
+        assert!(
+            reported.contains("`logo.png` does not exist"),
+            "{}",
+            reported
+        );
+        assert!(
+            reported.contains("link leaves the repository"),
+            "{}",
+            reported
+        );
+    }
+
+    #[tokio::test]
+    async fn images_are_checked() {
+        let files = files([
+            (
+                "apps/xgov-1.md",
+                b"---\nid: 1\n---\n\n\
+                ![Logo](logo.png) ![](photo.png) ![Chart](chart.svg) ![Missing](gone.png)\n"
+                    as &[u8],
+            ),
+            (
+                "apps/logo.png",
+                b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\0\x10\0\0\0\x10",
+            ),
+            (
+                "apps/photo.png",
+                b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR\0\0\x10\0\0\0\x10\0",
+            ),
+            ("apps/chart.svg", b"<svg onload=\"alert(1)\"></svg>"),
+        ]);
+
+        let reporter = Linter::new(Count::new(Text::<String>::default()))
+            .clear_lints()
+            .add_lint(
+                "markdown-images",
+                lints::markdown::Images {
+                    formats: vec!["png"],
+                    depth: 0,
+                    max_bytes: None,
+                    max_width: Some(1024),
+                    max_height: None,
+                },
+            )
+            .unwrap()
+            .set_fetch(files)
+            .check_file(Path::new("apps/xgov-1.md"))
+            .run()
+            .await
+            .unwrap();
+
+        let counts = reporter.counts();
+        let reported = reporter.into_inner().into_inner();
+
+        assert_eq!(counts.error, 5, "{}", reported);
+        assert!(reported.contains("image has no alt text"), "{}", reported);
+        assert!(
+            reported.contains("`photo.png` is too wide (4096 pixels, max 1024)"),
+            "{}",
+            reported
+        );
+        assert!(
+            reported.contains("`chart.svg` is a `svg` image"),
+            "{}",
+            reported
+        );
+        assert!(
+            reported.contains("`chart.svg` contains scripts"),
+            "{}",
+            reported
+        );
+        assert!(
+            reported.contains("`gone.png` does not exist"),
+            "{}",
+            reported
+        );
+    }
+
+    /// Lints each of the lint's examples, and checks they pass or fail as
+    /// promised.
+    async fn check_examples<F>(slug: &str, build: F)
+    where
+        F: Fn() -> Box<dyn Lint>,
+    {
+        for example in build().metadata().examples {
+            let reporter = example
+                .lint(Count::new(Text::<String>::default()), slug, build())
+                .await
+                .unwrap();
+
+            let counts = reporter.counts();
+            let reported = reporter.into_inner().into_inner();
+
+            match example.expect {
+                Expect::Pass => assert!(
+                    reported.is_empty(),
+                    "passing example for `{}` reported:\n{}",
+                    slug,
+                    reported,
+                ),
+                // Some lints only warn.
+                Expect::Fail => assert!(
+                    counts.error + counts.warning > 0,
+                    "failing example for `{}` did not report a problem:\n{}",
+                    slug,
+                    example.source,
+                ),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn default_lint_examples() {
+        for (slug, _) in default_lints() {
+            check_examples(slug, || {
+                default_lints().find(|(s, _)| *s == slug).unwrap().1
+            })
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn registry_lint_examples() {
+        for (kind, args) in registry::example_args() {
+            let build = || registry::build_lint(kind, args.clone()).unwrap();
+
+            let metadata = build().metadata();
+            assert!(
+                metadata.examples.iter().any(|e| e.expect == Expect::Pass),
+                "`{}` has no passing example",
+                kind
+            );
+            assert!(
+                metadata.examples.iter().any(|e| e.expect == Expect::Fail),
+                "`{}` has no failing example",
+                kind
+            );
+
+            check_examples(kind, build).await;
+        }
+    }
+}
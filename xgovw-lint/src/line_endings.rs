@@ -0,0 +1,93 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+const BOM: char = '\u{feff}';
+
+/// What [`normalize`] removed from a source to make it parseable.
+///
+/// Normalization never adds or removes lines, so line numbers in diagnostics
+/// are the same as in the original file. Columns are unchanged too, except on
+/// the first line when a byte order mark was stripped (it has no width when
+/// displayed anyway).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LineEndings {
+    bom: bool,
+    crlf: Vec<usize>,
+}
+
+impl LineEndings {
+    /// Whether the source started with a UTF-8 byte order mark.
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// Lines (starting at one) that ended with `\r\n` instead of `\n`.
+    pub fn crlf_lines(&self) -> &[usize] {
+        &self.crlf
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.bom && self.crlf.is_empty()
+    }
+}
+
+/// Strips a leading byte order mark and converts `\r\n` line endings to `\n`.
+///
+/// Lone carriage returns are left alone, since they don't appear in files
+/// written by any editor still in use.
+pub(crate) fn normalize(text: String) -> (String, LineEndings) {
+    let stripped = text.strip_prefix(BOM);
+    let bom = stripped.is_some();
+
+    if !bom && !text.contains("\r\n") {
+        return (text, LineEndings::default());
+    }
+
+    let text = stripped.unwrap_or(&text);
+    let mut crlf = Vec::new();
+    let mut output = String::with_capacity(text.len());
+
+    let mut lines = text.split('\n').enumerate().peekable();
+
+    while let Some((index, line)) = lines.next() {
+        // The last piece isn't followed by `\n`, so a `\r` there is lone.
+        let is_last = lines.peek().is_none();
+
+        match line.strip_suffix('\r') {
+            Some(line) if !is_last => {
+                crlf.push(index + 1);
+                output.push_str(line);
+            }
+            _ => output.push_str(line),
+        }
+
+        if !is_last {
+            output.push('\n');
+        }
+    }
+
+    (output, LineEndings { bom, crlf })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_lf_unchanged() {
+        let (text, endings) = normalize("---\nid: 1\n---\n".into());
+        assert_eq!(text, "---\nid: 1\n---\n");
+        assert!(endings.is_empty());
+    }
+
+    #[test]
+    fn normalize_crlf_and_bom() {
+        let (text, endings) = normalize("\u{feff}---\r\nid: 1\n---\r\n\rx\r".into());
+        assert_eq!(text, "---\nid: 1\n---\n\rx\r");
+        assert!(endings.has_bom());
+        assert_eq!(endings.crlf_lines(), &[1, 3]);
+    }
+}
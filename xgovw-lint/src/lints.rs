@@ -7,23 +7,30 @@
 pub mod markdown;
 pub mod preamble;
 
-use annotate_snippets::snippet::Snippet;
+use annotate_snippets::snippet::{AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use comrak::nodes::AstNode;
+use comrak::nodes::{Ast, AstNode};
 
+use crate::line_endings::LineEndings;
 use crate::preamble::Preamble;
 use crate::reporters::{self, Reporter};
+use crate::span::{Span, SpanSource, Spans};
+use crate::{ResourceNotFetchedSnafu, XGovNotFetchedSnafu, XGovWithoutOriginSnafu};
 
 use educe::Educe;
 
-use snafu::Snafu;
+use serde::Serialize;
+
+use snafu::{OptionExt, Snafu};
 
 use std::cell::RefCell;
 use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::ops::Deref;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::string::FromUtf8Error;
 
 #[derive(Debug, Snafu)]
@@ -33,9 +40,12 @@ pub enum Error {
     ReportFailed { source: reporters::Error },
     #[snafu(context(false))]
     InvalidUtf8 { source: std::str::Utf8Error },
+    #[snafu(display("{}", source))]
     Custom {
         source: Box<dyn std::error::Error + 'static>,
     },
+    #[snafu(display("{}", source))]
+    Linter { source: Box<crate::Error> },
 }
 
 impl Error {
@@ -49,6 +59,14 @@ impl Error {
     }
 }
 
+impl From<crate::Error> for Error {
+    fn from(e: crate::Error) -> Self {
+        Error::Linter {
+            source: Box::new(e),
+        }
+    }
+}
+
 impl From<FromUtf8Error> for Error {
     fn from(e: FromUtf8Error) -> Self {
         Error::InvalidUtf8 {
@@ -64,6 +82,8 @@ pub(crate) struct InnerContext<'a> {
     pub(crate) body_source: &'a str,
     pub(crate) body: &'a AstNode<'a>,
     pub(crate) origin: Option<&'a str>,
+    pub(crate) line_endings: &'a LineEndings,
+    pub(crate) spans: Rc<Spans>,
 }
 
 #[derive(Educe)]
@@ -74,6 +94,7 @@ where
 {
     pub(crate) inner: InnerContext<'a>,
     pub(crate) xgovs: &'b HashMap<&'b Path, Result<InnerContext<'b>, &'b crate::Error>>,
+    pub(crate) resources: &'b HashMap<PathBuf, Result<Vec<u8>, crate::Error>>,
     #[educe(Debug(ignore))]
     pub(crate) reporter: &'b dyn Reporter,
 }
@@ -99,6 +120,11 @@ where
             .unwrap()
     }
 
+    /// The whole (normalized) source, including the preamble.
+    pub(crate) fn source(&self) -> &'a str {
+        self.inner.source
+    }
+
     /// XXX: comrak doesn't include a source field with its `AstNode`, so use
     ///      this instead. Don't expose it publicly since it's really hacky.
     pub(crate) fn source_for_text(&self, line: u32, buf: &[u8]) -> String {
@@ -127,15 +153,122 @@ where
         self.inner.origin
     }
 
+    /// Where `ast`, a node from [`Context::body`], appears in the source.
+    pub fn span(&self, ast: &Ast) -> Option<Span> {
+        self.inner.spans.get(ast)
+    }
+
+    /// Narrow the span of a node down to `range`, a byte range within `text`
+    /// (the node's literal text). Falls back to the span of the whole node
+    /// when the text doesn't appear verbatim in the source (eg. because of
+    /// escapes.)
+    pub fn text_span(&self, ast: &Ast, text: &[u8], range: Range<usize>) -> Option<Span> {
+        let span = self.span(ast)?;
+        let bytes = span.bytes();
+        let source = self.inner.source;
+
+        if range.end > text.len() {
+            return Some(span);
+        }
+
+        // Nodes like code blocks have delimiters around their text.
+        let offset = if bytes.len() == text.len() {
+            0
+        } else {
+            match std::str::from_utf8(text)
+                .ok()
+                .and_then(|t| source[bytes.clone()].find(t))
+            {
+                Some(o) if !text.is_empty() => o,
+                _ => return Some(span),
+            }
+        };
+
+        let start = bytes.start + offset + range.start;
+        let end = bytes.start + offset + range.end;
+
+        if !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+            return Some(span);
+        }
+
+        Some(self.inner.spans.span(source, start..end))
+    }
+
+    /// The lines covered by `span`, for use in a diagnostic.
+    pub fn span_source(&self, span: Span) -> SpanSource<'a> {
+        self.inner.spans.source(self.inner.source, span)
+    }
+
+    /// A slice underlining `span` with `label`.
+    pub fn span_slice<'s>(
+        &self,
+        span: Span,
+        annotation_type: AnnotationType,
+        label: &'s str,
+    ) -> Slice<'s>
+    where
+        'a: 's,
+    {
+        let located = self.span_source(span);
+        Slice {
+            line_start: located.line_start,
+            fold: false,
+            origin: self.origin(),
+            source: located.source,
+            annotations: vec![SourceAnnotation {
+                annotation_type,
+                label,
+                range: located.range,
+            }],
+        }
+    }
+
+    /// A slice underlining `ast` with `label`, falling back to its whole
+    /// first line (without an annotation) when its span isn't known.
+    pub fn slice_for<'s>(
+        &self,
+        ast: &Ast,
+        annotation_type: AnnotationType,
+        label: &'s str,
+    ) -> Slice<'s>
+    where
+        'a: 's,
+    {
+        match self.span(ast) {
+            Some(span) => self.span_slice(span, annotation_type, label),
+            None => Slice {
+                line_start: usize::try_from(ast.start_line).unwrap(),
+                fold: false,
+                origin: self.origin(),
+                source: self.line(ast.start_line),
+                annotations: vec![],
+            },
+        }
+    }
+
+    /// What was changed in the source to make it parseable. See
+    /// [`Linter::normalize_line_endings`](crate::Linter::normalize_line_endings).
+    pub fn line_endings(&self) -> &'a LineEndings {
+        self.inner.line_endings
+    }
+
     pub fn report(&self, snippet: Snippet<'_>) -> Result<(), Error> {
         self.reporter.report(snippet)?;
         Ok(())
     }
 
-    pub fn xgov(&self, path: &Path) -> Result<Context<'b, 'b>, &crate::Error> {
-        let origin = self
-            .origin()
-            .expect("lint attempted to access an external resource without having an origin");
+    /// Get the context of another proposal, previously requested with
+    /// [`FetchContext::fetch`].
+    ///
+    /// The outer `Result` fails when the proposal cannot be looked up at all
+    /// (eg. it was never requested), which is a bug in the lint. The inner
+    /// `Result` fails when the proposal couldn't be read or parsed, which
+    /// should be reported as a diagnostic.
+    pub fn xgov(
+        &self,
+        path: &Path,
+    ) -> Result<Result<Context<'b, 'b>, &'b crate::Error>, crate::Error> {
+        let origin = self.origin().context(XGovWithoutOriginSnafu { path })?;
 
         let origin_path = PathBuf::from(origin);
         let root = origin_path.parent().unwrap_or_else(|| Path::new("."));
@@ -143,15 +276,37 @@ where
         let key = root.join(path);
         let inner = match self.xgovs.get(key.as_path()) {
             Some(Ok(i)) => i,
-            Some(Err(e)) => return Err(e),
-            None => panic!("no xgov found for key `{}`", key.display()),
+            Some(Err(e)) => return Ok(Err(e)),
+            None => return XGovNotFetchedSnafu { path: key }.fail(),
         };
 
-        Ok(Context {
+        Ok(Ok(Context {
             inner: inner.clone(),
             xgovs: self.xgovs,
+            resources: self.resources,
             reporter: self.reporter,
-        })
+        }))
+    }
+
+    /// Get the contents of a file, previously requested with
+    /// [`FetchContext::fetch_resource`].
+    ///
+    /// The `Result`s fail the same way as [`Context::xgov`].
+    pub fn resource(
+        &self,
+        path: &Path,
+    ) -> Result<Result<&'b [u8], &'b crate::Error>, crate::Error> {
+        let origin = self.origin().context(XGovWithoutOriginSnafu { path })?;
+
+        let origin_path = PathBuf::from(origin);
+        let root = origin_path.parent().unwrap_or_else(|| Path::new("."));
+
+        let key = root.join(path);
+        match self.resources.get(&key) {
+            Some(Ok(b)) => Ok(Ok(b)),
+            Some(Err(e)) => Ok(Err(e)),
+            None => ResourceNotFetchedSnafu { path: key }.fail(),
+        }
     }
 }
 
@@ -160,6 +315,7 @@ pub struct FetchContext<'a> {
     pub(crate) preamble: &'a Preamble<'a>,
     pub(crate) body: &'a AstNode<'a>,
     pub(crate) xgovs: RefCell<HashSet<PathBuf>>,
+    pub(crate) resources: RefCell<HashSet<PathBuf>>,
 }
 
 impl<'a> FetchContext<'a> {
@@ -174,6 +330,158 @@ impl<'a> FetchContext<'a> {
     pub fn fetch(&self, path: PathBuf) {
         self.xgovs.borrow_mut().insert(path);
     }
+
+    /// Like [`FetchContext::fetch`], but for any kind of file. The file is
+    /// read as-is instead of being parsed as a proposal.
+    pub fn fetch_resource(&self, path: PathBuf) {
+        self.resources.borrow_mut().insert(path);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Expect {
+    /// The example must not produce any diagnostics.
+    Pass,
+
+    /// The example must produce at least one diagnostic.
+    Fail,
+}
+
+/// A complete document demonstrating the behaviour of a lint.
+#[derive(Debug, Clone, Serialize)]
+pub struct Example {
+    pub expect: Expect,
+
+    /// Path the document is linted as, for lints that depend on the file name.
+    pub origin: Option<String>,
+
+    pub source: String,
+
+    /// Other documents the example refers to, by path relative to `origin`.
+    /// They're only read, not linted.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<(String, String)>,
+}
+
+impl Example {
+    pub fn pass<S>(source: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            expect: Expect::Pass,
+            origin: None,
+            source: source.into(),
+            files: vec![],
+        }
+    }
+
+    pub fn fail<S>(source: S) -> Self
+    where
+        S: Into<String>,
+    {
+        Self {
+            expect: Expect::Fail,
+            origin: None,
+            source: source.into(),
+            files: vec![],
+        }
+    }
+
+    pub fn with_origin<S>(mut self, origin: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.origin = Some(origin.into());
+        self
+    }
+
+    pub fn with_file<P, S>(mut self, path: P, source: S) -> Self
+    where
+        P: Into<String>,
+        S: Into<String>,
+    {
+        self.files.push((path.into(), source.into()));
+        self
+    }
+
+    /// Lints the example with only `lint` enabled.
+    pub async fn lint<R>(
+        &self,
+        reporter: R,
+        slug: &str,
+        lint: Box<dyn Lint>,
+    ) -> Result<R, crate::Error>
+    where
+        R: Reporter,
+    {
+        let linter = crate::Linter::new(reporter)
+            .clear_lints()
+            .add_lint(slug, lint)?;
+
+        if self.files.is_empty() {
+            return linter
+                .check_slice(self.origin.as_deref(), &self.source)
+                .run()
+                .await;
+        }
+
+        // Documents given as strings can't refer to others, so serve the
+        // example and its files as if they were on disk.
+        let origin = Path::new(self.origin.as_deref().unwrap_or("example.md"));
+        let root = origin.parent().unwrap_or_else(|| Path::new(""));
+
+        let files = self
+            .files
+            .iter()
+            .map(|(path, source)| (root.join(path), source.clone().into_bytes()))
+            .chain(std::iter::once((
+                origin.to_path_buf(),
+                self.source.clone().into_bytes(),
+            )))
+            .collect();
+
+        linter
+            .set_fetch(crate::fetch::Memory(files))
+            .check_file(origin)
+            .run()
+            .await
+    }
+}
+
+/// A configured value of a lint, shown alongside its explanation.
+#[derive(Debug, Clone, Serialize)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub value: String,
+}
+
+impl Parameter {
+    pub fn new<V>(name: &'static str, value: V) -> Self
+    where
+        V: Debug,
+    {
+        Self {
+            name,
+            value: format!("{:?}", value),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Metadata {
+    /// One sentence describing what the lint checks.
+    pub summary: String,
+
+    /// Why the check exists.
+    pub rationale: String,
+
+    pub parameters: Vec<Parameter>,
+
+    /// Documents that are linted by `xgovw`'s test suite to ensure they
+    /// match the lint's actual behaviour.
+    pub examples: Vec<Example>,
 }
 
 pub trait Lint: Debug {
@@ -182,6 +490,14 @@ pub trait Lint: Debug {
     }
 
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error>;
+
+    /// Describes the lint. The default has no description or examples.
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "Undocumented lint.".into(),
+            ..Default::default()
+        }
+    }
 }
 
 impl Lint for Box<dyn Lint> {
@@ -194,6 +510,11 @@ impl Lint for Box<dyn Lint> {
         let lint: &dyn Lint = self.deref();
         lint.lint(slug, ctx)
     }
+
+    fn metadata(&self) -> Metadata {
+        let lint: &dyn Lint = self.deref();
+        lint.metadata()
+    }
 }
 
 pub(crate) trait LintExt: Lint {
@@ -4,16 +4,56 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+pub mod blank_lines;
+pub mod boilerplate;
+pub mod confusables;
+pub mod external_links;
+pub mod final_newline;
+pub mod headings;
+pub mod html;
+pub mod images;
+pub mod line_endings;
+pub mod line_length;
+mod lines;
 pub mod link_first;
 pub mod link_status;
+pub mod link_targets;
+pub mod link_text;
+pub mod privacy;
 pub mod regex;
 pub mod relative_links;
+pub mod secrets;
+pub mod section_length;
 pub mod section_order;
 pub mod section_required;
+mod sections;
+pub mod social_profiles;
+pub mod tables;
+pub mod tabs;
+pub mod trailing_whitespace;
 
+pub use self::blank_lines::BlankLines;
+pub use self::boilerplate::Boilerplate;
+pub use self::confusables::Confusables;
+pub use self::external_links::ExternalLinks;
+pub use self::final_newline::FinalNewline;
+pub use self::headings::Headings;
+pub use self::html::Html;
+pub use self::images::Images;
+pub use self::line_endings::LineEndings;
+pub use self::line_length::LineLength;
 pub use self::link_first::LinkFirst;
 pub use self::link_status::LinkStatus;
+pub use self::link_targets::LinkTargets;
+pub use self::link_text::LinkText;
+pub use self::privacy::Privacy;
 pub use self::regex::Regex;
 pub use self::relative_links::RelativeLinks;
+pub use self::secrets::Secrets;
+pub use self::section_length::SectionLength;
 pub use self::section_order::SectionOrder;
 pub use self::section_required::SectionRequired;
+pub use self::social_profiles::SocialProfiles;
+pub use self::tables::Tables;
+pub use self::tabs::Tabs;
+pub use self::trailing_whitespace::TrailingWhitespace;
@@ -0,0 +1,83 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
+
+use super::lines::{self, Block};
+
+/// There are no consecutive blank lines in the body, except in code blocks.
+///
+/// Blank lines at the very end of the file are left to
+/// [`FinalNewline`](super::FinalNewline).
+#[derive(Debug, Deserialize)]
+pub struct BlankLines;
+
+impl Lint for BlankLines {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let lines = lines::lines(ctx);
+
+        let end = match lines.iter().rposition(|line| !line.is_blank()) {
+            Some(e) => e,
+            None => return Ok(()),
+        };
+
+        // Each run of extra blank lines, shown after the line before it.
+        let is_extra = |i: usize| {
+            lines[i - 1..=i]
+                .iter()
+                .all(|l| l.is_blank() && l.block != Block::Code)
+        };
+
+        let mut found = vec![];
+        let mut index = 1;
+
+        while index < end {
+            if !is_extra(index) {
+                index += 1;
+                continue;
+            }
+
+            let first = index;
+            while index < end && is_extra(index) {
+                index += 1;
+            }
+
+            // Empty lines at the end of a slice aren't shown, so show the
+            // line after the run as well.
+            let source = ctx.body_source();
+            let before = &lines[first.saturating_sub(2)];
+            let line = before.through(source, &lines[index]);
+            let start = source[before.offset..lines[first].offset].chars().count();
+
+            found.push((line, (start, start + 1)));
+        }
+
+        lines::report(
+            ctx,
+            slug,
+            "the body must not contain consecutive blank lines",
+            &found,
+            "extra blank line",
+            "remove the extra blank lines",
+        )
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "There are no consecutive blank lines in the body.".into(),
+            rationale: "Extra blank lines don't change how the application is rendered, but \
+                produce noisy diffs."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\nHello\n\nworld.\n"),
+                Example::fail("---\nid: 1\n---\n\nHello\n\n\nworld.\n"),
+            ],
+        }
+    }
+}
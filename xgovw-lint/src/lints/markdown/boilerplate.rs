@@ -0,0 +1,263 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use comrak::Arena;
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::preamble::{Preamble, SplitError};
+
+use serde::Deserialize;
+
+use snafu::{ResultExt, Snafu};
+
+use std::cell::RefCell;
+use std::fmt::{Debug, Display};
+use std::rc::Rc;
+
+use super::sections;
+
+/// How similar a section has to be to the template's to count as copied, when
+/// not configured otherwise.
+const SIMILARITY: f64 = 0.9;
+
+#[derive(Debug, Snafu)]
+enum TemplateError {
+    #[snafu(display("template is missing its preamble"))]
+    Split { source: SplitError },
+    #[snafu(display("template preamble is malformed"))]
+    Malformed,
+}
+
+/// The parts of a template that applications are compared against.
+#[derive(Debug)]
+struct Template {
+    /// Values of the placeholder headers, by header name.
+    placeholders: Vec<(String, String)>,
+
+    /// Plain text of each non-empty section, by heading.
+    sections: Vec<(String, String)>,
+}
+
+impl Template {
+    fn parse<S>(source: &str, fields: &[S]) -> Result<Self, TemplateError>
+    where
+        S: AsRef<str>,
+    {
+        let (preamble_source, body_source) = Preamble::split(source).context(SplitSnafu)?;
+
+        let preamble =
+            Preamble::parse(None, preamble_source).map_err(|_| TemplateError::Malformed)?;
+
+        let placeholders = fields
+            .iter()
+            .map(AsRef::as_ref)
+            .filter_map(|name| preamble.by_name(name))
+            .map(|f| (f.name().to_owned(), f.value().trim().to_owned()))
+            .collect();
+
+        let arena = Arena::new();
+        let body = comrak::parse_document(&arena, body_source, &crate::comrak_options());
+
+        let sections = sections::headings(body)
+            .into_iter()
+            .map(|h| (h.text, sections::plain_text(sections::content(h.node))))
+            .filter(|(_, text)| !text.is_empty())
+            .collect();
+
+        Ok(Self {
+            placeholders,
+            sections,
+        })
+    }
+}
+
+/// Content copied from a template application without being replaced.
+///
+/// Sections are compared by their plain text, so changes to formatting alone
+/// don't count. Preamble headers listed in `fields` are placeholders, and must
+/// not keep the template's value.
+#[derive(Debug, Deserialize)]
+pub struct Boilerplate<S> {
+    pub template: S,
+    pub fields: Vec<S>,
+    pub similarity: Option<f64>,
+
+    /// `template`, parsed the first time the lint runs.
+    #[serde(skip)]
+    parsed: RefCell<Option<Rc<Template>>>,
+}
+
+impl<S> Boilerplate<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    /// Creates a lint that compares applications against `template`.
+    pub fn new(template: S, fields: Vec<S>, similarity: Option<f64>) -> Self {
+        Self {
+            template,
+            fields,
+            similarity,
+            parsed: Default::default(),
+        }
+    }
+
+    fn parsed(&self) -> Result<Rc<Template>, Error> {
+        let mut parsed = self.parsed.borrow_mut();
+
+        if let Some(template) = &*parsed {
+            return Ok(template.clone());
+        }
+
+        let template = Template::parse(self.template.as_ref(), &self.fields)
+            .map(Rc::new)
+            .map_err(Error::custom)?;
+
+        *parsed = Some(template.clone());
+        Ok(template)
+    }
+
+    fn lint_preamble<'a, 'b>(
+        &self,
+        slug: &'a str,
+        ctx: &Context<'a, 'b>,
+        template: &Template,
+    ) -> Result<(), Error> {
+        for (name, placeholder) in &template.placeholders {
+            let field = match ctx.preamble().by_name(name) {
+                Some(f) => f,
+                None => continue,
+            };
+
+            let value = field.value().trim();
+            if value.is_empty() || value != placeholder {
+                continue;
+            }
+
+            let label = format!(
+                "preamble header `{}` still has the template's placeholder value",
+                name
+            );
+
+            let leading = field.value().len() - field.value().trim_start().len();
+            let (start, end) = field.annotation_range(leading..leading + value.len());
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&label),
+                }),
+                slices: vec![Slice {
+                    fold: false,
+                    line_start: field.line_start(),
+                    origin: ctx.origin(),
+                    source: field.source(),
+                    annotations: vec![SourceAnnotation {
+                        annotation_type: AnnotationType::Error,
+                        label: "copied from the template",
+                        range: (start, end),
+                    }],
+                }],
+                footer: vec![],
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<S> Lint for Boilerplate<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let template = self.parsed()?;
+
+        self.lint_preamble(slug, ctx, &template)?;
+
+        let expected = &template.sections;
+        let names: Vec<_> = expected.iter().map(|(name, _)| name.as_str()).collect();
+        let headings = sections::headings(ctx.body());
+        let matches = sections::match_sections(&headings, &names);
+
+        let threshold = self.similarity.unwrap_or(SIMILARITY);
+
+        for (heading, matched) in headings.iter().zip(matches) {
+            let name = match matched {
+                Some(m) => m.name,
+                None => continue,
+            };
+
+            let (_, template_text) = expected.iter().find(|(n, _)| n == name).unwrap();
+            let text = sections::plain_text(sections::content(heading.node));
+
+            if text.is_empty() {
+                continue;
+            }
+
+            let label = if text == *template_text {
+                format!("section `{}` is unchanged from the template", name)
+            } else {
+                let similarity =
+                    strsim::sorensen_dice(&text.to_lowercase(), &template_text.to_lowercase());
+
+                if similarity < threshold {
+                    continue;
+                }
+
+                format!(
+                    "section `{}` is nearly identical to the template ({:.0}% similar)",
+                    name,
+                    similarity * 100.0
+                )
+            };
+
+            let data = heading.node.data.borrow();
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&label),
+                }),
+                slices: vec![ctx.slice_for(
+                    &data,
+                    AnnotationType::Error,
+                    "copied from the template",
+                )],
+                footer: vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some("replace the example text with your own"),
+                }],
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "The application doesn't contain text copied from the template.".into(),
+            rationale: "Placeholder text left in an application tells reviewers nothing about \
+                the applicant."
+                .into(),
+            parameters: vec![
+                Parameter::new("fields", &self.fields),
+                Parameter::new("similarity", self.similarity.unwrap_or(SIMILARITY)),
+            ],
+            examples: vec![
+                Example::pass(
+                    "---\nid: 1\n---\n\n## Introduction\n\nI maintain an open source wallet.\n",
+                ),
+                Example::fail(self.template.to_string()),
+            ],
+        }
+    }
+}
@@ -0,0 +1,478 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use comrak::nodes::{Ast, NodeCode, NodeCodeBlock, NodeHtmlBlock};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::tree::{self, Next, TraverseExt};
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+/// Characters that aren't visible, or that change the direction of the text
+/// around them.
+const INVISIBLE: &[(char, &str)] = &[
+    ('\u{00ad}', "SOFT HYPHEN"),
+    ('\u{061c}', "ARABIC LETTER MARK"),
+    ('\u{180e}', "MONGOLIAN VOWEL SEPARATOR"),
+    ('\u{200b}', "ZERO WIDTH SPACE"),
+    ('\u{200c}', "ZERO WIDTH NON-JOINER"),
+    ('\u{200d}', "ZERO WIDTH JOINER"),
+    ('\u{200e}', "LEFT-TO-RIGHT MARK"),
+    ('\u{200f}', "RIGHT-TO-LEFT MARK"),
+    ('\u{202a}', "LEFT-TO-RIGHT EMBEDDING"),
+    ('\u{202b}', "RIGHT-TO-LEFT EMBEDDING"),
+    ('\u{202c}', "POP DIRECTIONAL FORMATTING"),
+    ('\u{202d}', "LEFT-TO-RIGHT OVERRIDE"),
+    ('\u{202e}', "RIGHT-TO-LEFT OVERRIDE"),
+    ('\u{2060}', "WORD JOINER"),
+    ('\u{2061}', "FUNCTION APPLICATION"),
+    ('\u{2062}', "INVISIBLE TIMES"),
+    ('\u{2063}', "INVISIBLE SEPARATOR"),
+    ('\u{2064}', "INVISIBLE PLUS"),
+    ('\u{2066}', "LEFT-TO-RIGHT ISOLATE"),
+    ('\u{2067}', "RIGHT-TO-LEFT ISOLATE"),
+    ('\u{2068}', "FIRST STRONG ISOLATE"),
+    ('\u{2069}', "POP DIRECTIONAL ISOLATE"),
+    ('\u{feff}', "ZERO WIDTH NO-BREAK SPACE"),
+];
+
+/// Letters from other scripts that look like Latin ones, after lowercasing.
+const HOMOGLYPHS: &[(char, &str)] = &[
+    // Cyrillic
+    ('а', "a"),
+    ('в', "b"),
+    ('ԁ', "d"),
+    ('е', "e"),
+    ('ё', "e"),
+    ('һ', "h"),
+    ('і', "i"),
+    ('ї', "i"),
+    ('ј', "j"),
+    ('к', "k"),
+    ('ӏ', "l"),
+    ('о', "o"),
+    ('р', "p"),
+    ('ԛ', "q"),
+    ('ѕ', "s"),
+    ('с', "c"),
+    ('у', "y"),
+    ('ԝ', "w"),
+    ('х', "x"),
+    // Greek
+    ('α', "a"),
+    ('ι', "i"),
+    ('κ', "k"),
+    ('ν', "v"),
+    ('ο', "o"),
+    ('ρ', "p"),
+    ('υ', "u"),
+    ('χ', "x"),
+    // Latin and symbols (but not digits, which handles often use on purpose)
+    ('ı', "i"),
+    ('ɡ', "g"),
+    ('ℓ', "l"),
+];
+
+/// Identifiers shorter than this are too likely to collide by accident.
+const MIN_COLLISION: usize = 3;
+
+/// Hidden characters and lookalike names.
+///
+/// The whole document (including code) is checked for invisible and
+/// bidirectional control characters. The preamble headers in `names`, and
+/// `@handles` in the body, are checked for words that mix scripts (like Latin
+/// and Cyrillic), and for words that look the same as another one in the
+/// document or in `known` without being the same.
+#[derive(Debug, Deserialize)]
+pub struct Confusables<S> {
+    pub names: Vec<S>,
+    #[serde(default)]
+    pub known: Vec<S>,
+}
+
+/// A name or handle, and where it is.
+struct Word<'a> {
+    text: String,
+    line_start: usize,
+    source: &'a str,
+
+    /// Offset of the word in `source`, in characters.
+    start: usize,
+}
+
+impl<S> Confusables<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint_invisible<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        for (index, line) in ctx.source().split('\n').enumerate() {
+            let found: Vec<_> = line
+                .chars()
+                .enumerate()
+                .filter_map(|(i, c)| invisible(c).map(|name| (i, c, name)))
+                .collect();
+
+            if found.is_empty() {
+                continue;
+            }
+
+            let labels: Vec<_> = found
+                .iter()
+                .map(|(_, c, name)| format!("U+{:04X} {}", u32::from(*c), name))
+                .collect();
+
+            let title = match found.len() {
+                1 => "invisible or bidirectional control character".to_owned(),
+                n => format!("{} invisible or bidirectional control characters", n),
+            };
+
+            let source = visible(line);
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&title),
+                }),
+                slices: vec![Slice {
+                    line_start: index + 1,
+                    fold: false,
+                    origin: ctx.origin(),
+                    source: &source,
+                    annotations: found
+                        .iter()
+                        .zip(&labels)
+                        .map(|((i, _, _), label)| SourceAnnotation {
+                            annotation_type: AnnotationType::Error,
+                            label,
+                            range: (*i, *i + 1),
+                        })
+                        .collect(),
+                }],
+                footer: vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some("these characters can hide or reorder text, so remove them"),
+                }],
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn words<'a, 'b>(&self, ctx: &Context<'a, 'b>) -> Result<Vec<Word<'a>>, Error> {
+        let re = Regex::new(r"[\p{L}\p{M}\p{N}_\-]+").unwrap();
+        let mut words = vec![];
+
+        for name in &self.names {
+            let field = match ctx.preamble().by_name(name.as_ref()) {
+                Some(f) => f,
+                None => continue,
+            };
+
+            let offset = field.name().chars().count() + 1;
+
+            for matched in re.find_iter(field.value()) {
+                words.push(Word {
+                    text: matched.as_str().to_owned(),
+                    line_start: field.line_start(),
+                    source: field.source(),
+                    start: offset + field.value()[..matched.start()].chars().count(),
+                });
+            }
+        }
+
+        let mut visitor = Visitor {
+            ctx,
+            re: Regex::new(r"@([\p{L}\p{M}\p{N}_\-]+)").unwrap(),
+            words,
+        };
+
+        ctx.body().traverse().visit(&mut visitor)?;
+
+        Ok(visitor.words)
+    }
+
+    fn report<'a, 'b>(
+        &self,
+        slug: &'a str,
+        ctx: &Context<'a, 'b>,
+        title: &str,
+        word: &Word<'a>,
+        marks: &[(usize, String)],
+    ) -> Result<(), Error> {
+        let source = visible(word.source);
+
+        ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(slug),
+                label: Some(title),
+            }),
+            slices: vec![Slice {
+                line_start: word.line_start,
+                fold: false,
+                origin: ctx.origin(),
+                source: &source,
+                annotations: marks
+                    .iter()
+                    .map(|(offset, label)| SourceAnnotation {
+                        annotation_type: AnnotationType::Error,
+                        label,
+                        range: (word.start + offset, word.start + offset + 1),
+                    })
+                    .collect(),
+            }],
+            footer: vec![],
+            opt: Default::default(),
+        })?;
+
+        Ok(())
+    }
+}
+
+impl<S> Lint for Confusables<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        self.lint_invisible(slug, ctx)?;
+
+        let words = self.words(ctx)?;
+
+        let mut others: Vec<_> = self.known.iter().map(|k| k.as_ref().to_owned()).collect();
+        others.extend(words.iter().map(|w| w.text.clone()));
+
+        for word in &words {
+            let scripts: Vec<_> = word.text.chars().map(script).collect();
+
+            // Marks the letters that aren't in the word's main script.
+            let main = majority(&scripts);
+            let marks: Vec<_> = word
+                .text
+                .chars()
+                .zip(&scripts)
+                .enumerate()
+                .filter(|(_, (_, s))| s.is_some() && **s != main)
+                .map(|(i, (c, s))| (i, format!("{} U+{:04X}", s.unwrap(), u32::from(c))))
+                .collect();
+
+            if !marks.is_empty() {
+                let title = format!("`{}` mixes letters from different scripts", word.text);
+                self.report(slug, ctx, &title, word, &marks)?;
+                continue;
+            }
+
+            if word.text.chars().count() < MIN_COLLISION {
+                continue;
+            }
+
+            let mine = skeleton(&word.text);
+            let lookalike = others
+                .iter()
+                .find(|o| !o.to_lowercase().eq(&word.text.to_lowercase()) && skeleton(o) == mine);
+
+            let lookalike = match lookalike {
+                Some(l) => l,
+                None => continue,
+            };
+
+            let marks: Vec<_> = word
+                .text
+                .chars()
+                .enumerate()
+                .filter(|(_, c)| HOMOGLYPHS.iter().any(|(h, _)| h == c))
+                .map(|(i, c)| (i, format!("U+{:04X}", u32::from(c))))
+                .collect();
+
+            // Only the lookalike has the unusual characters.
+            if marks.is_empty() {
+                continue;
+            }
+
+            let title = format!("`{}` looks like `{}`", word.text, lookalike);
+            self.report(slug, ctx, &title, word, &marks)?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let name = self
+            .names
+            .first()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "author".into());
+
+        Metadata {
+            summary: "There are no hidden characters, or names that imitate other names.".into(),
+            rationale: "Invisible characters and lookalike letters can disguise text, or \
+                impersonate another candidate."
+                .into(),
+            parameters: vec![
+                Parameter::new("names", &self.names),
+                Parameter::new("known", &self.known),
+            ],
+            examples: vec![
+                Example::pass(format!(
+                    "---\n{}: Alice (@alice)\n---\n\nThanks to @bob for the review.\n",
+                    name
+                )),
+                Example::fail(format!(
+                    "---\n{}: Alice (@\u{0430}lice)\n---\n\nThanks to @bob.\n",
+                    name
+                )),
+                Example::fail(format!(
+                    "---\n{}: Alice (@alice)\n---\n\nThanks to @bob\u{202e}.\n",
+                    name
+                )),
+            ],
+        }
+    }
+}
+
+struct Visitor<'a, 'b, 'c> {
+    ctx: &'c Context<'a, 'b>,
+    re: Regex,
+    words: Vec<Word<'a>>,
+}
+
+impl<'a, 'b, 'c> tree::Visitor for Visitor<'a, 'b, 'c> {
+    type Error = Error;
+
+    fn enter_code(&mut self, _: &Ast, _: &NodeCode) -> Result<Next, Self::Error> {
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_code_block(&mut self, _: &Ast, _: &NodeCodeBlock) -> Result<Next, Self::Error> {
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_html_block(&mut self, _: &Ast, _: &NodeHtmlBlock) -> Result<Next, Self::Error> {
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_text(&mut self, ast: &Ast, txt: &[u8]) -> Result<Next, Self::Error> {
+        let text = String::from_utf8_lossy(txt);
+
+        for captures in self.re.captures_iter(&text) {
+            let handle = captures.get(1).unwrap();
+
+            let span = match self.ctx.text_span(ast, txt, handle.range()) {
+                Some(s) => s,
+                None => continue,
+            };
+
+            let located = self.ctx.span_source(span);
+
+            self.words.push(Word {
+                text: handle.as_str().to_owned(),
+                line_start: located.line_start,
+                source: located.source,
+                start: located.range.0,
+            });
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+}
+
+fn invisible(c: char) -> Option<&'static str> {
+    INVISIBLE
+        .iter()
+        .find(|(i, _)| *i == c)
+        .map(|(_, name)| *name)
+}
+
+/// Replaces invisible characters with `�`, so they don't garble the output.
+fn visible(text: &str) -> String {
+    text.chars()
+        .map(|c| match invisible(c) {
+            Some(_) => '\u{fffd}',
+            None => c,
+        })
+        .collect()
+}
+
+/// The script a letter belongs to, for the scripts that have lookalikes of
+/// Latin letters. Everything else is `None`.
+fn script(c: char) -> Option<&'static str> {
+    match c {
+        'a'..='z' | 'A'..='Z' | '\u{c0}'..='\u{24f}' | '\u{1e00}'..='\u{1eff}' => Some("Latin"),
+        '\u{370}'..='\u{3ff}' | '\u{1f00}'..='\u{1fff}' => Some("Greek"),
+        '\u{400}'..='\u{52f}' => Some("Cyrillic"),
+        '\u{530}'..='\u{58f}' => Some("Armenian"),
+        _ => None,
+    }
+}
+
+/// The most common script in a word. Ties go to the script seen first.
+fn majority(scripts: &[Option<&'static str>]) -> Option<&'static str> {
+    let mut counts: Vec<(&'static str, usize)> = vec![];
+
+    for s in scripts.iter().flatten() {
+        match counts.iter_mut().find(|(n, _)| n == s) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((s, 1)),
+        }
+    }
+
+    counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(name, _)| *name)
+}
+
+/// Reduces `text` to what it looks like, so lookalikes have the same skeleton.
+fn skeleton(text: &str) -> String {
+    let mut out = String::new();
+
+    for c in text.chars().flat_map(char::to_lowercase) {
+        // Fullwidth forms, like `ａ`.
+        let c = match c {
+            '\u{ff01}'..='\u{ff5e}' => char::from_u32(u32::from(c) - 0xfee0).unwrap(),
+            c => c,
+        };
+
+        match HOMOGLYPHS.iter().find(|(h, _)| *h == c) {
+            Some((_, latin)) => out.push_str(latin),
+            None if invisible(c).is_some() => (),
+            None => out.push(c),
+        }
+    }
+
+    out.replace("rn", "m").replace("vv", "w")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skeletons_match_lookalikes() {
+        assert_eq!(skeleton("\u{0430}lice"), skeleton("alice"));
+        assert_eq!(skeleton("mode\u{200b}rn"), skeleton("modem"));
+        assert_eq!(skeleton("ＳＵＤＯ"), skeleton("sudo"));
+        assert_ne!(skeleton("alice"), skeleton("alicia"));
+    }
+
+    #[test]
+    fn majority_prefers_common_script() {
+        let scripts: Vec<_> = "\u{0430}lice".chars().map(script).collect();
+        assert_eq!(majority(&scripts), Some("Latin"));
+
+        let scripts: Vec<_> = "\u{0430}\u{0431}c".chars().map(script).collect();
+        assert_eq!(majority(&scripts), Some("Cyrillic"));
+    }
+}
@@ -0,0 +1,258 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
+
+use comrak::nodes::NodeValue;
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+use url::{Host, ParseError, Url};
+
+/// Schemes that run code or embed content when followed, so are never allowed.
+const DANGEROUS: &[&str] = &["javascript", "data", "vbscript"];
+
+/// Absolute URLs in links and images follow a policy.
+///
+/// Only the given `schemes` are allowed (`http` is reported separately when
+/// `https` is allowed.) Hosts must not be IP addresses, must not be in `deny`,
+/// and must be in `allow` unless it's empty. Domains also match their
+/// subdomains, so `github.com` matches `gist.github.com`.
+///
+/// Scheme-relative links (`//host/path`) count as external, and must name a
+/// scheme. Other relative links are left to `RelativeLinks` and `LinkTargets`.
+#[derive(Debug, Deserialize)]
+pub struct ExternalLinks<S> {
+    pub schemes: Vec<S>,
+    #[serde(default)]
+    pub allow: Vec<S>,
+    #[serde(default)]
+    pub deny: Vec<S>,
+}
+
+impl<S> ExternalLinks<S>
+where
+    S: Display + AsRef<str>,
+{
+    /// What's wrong with `url`, if anything, and how to fix it.
+    fn problem(&self, url: &str) -> Option<(String, Option<String>)> {
+        // Scheme-relative links (`//host/path`) are external links that use
+        // the page's scheme. Browsers treat `\\` like `/` here.
+        if url.starts_with(['/', '\\']) && url[1..].starts_with(['/', '\\']) {
+            let url = match Url::parse(&format!("https:{}", url)) {
+                Ok(u) => u,
+                Err(e) => return Some((format!("link is not a valid URL: {}", e), None)),
+            };
+
+            return self.host_problem(&url).or_else(|| {
+                let help = if self.schemes.iter().any(|s| s.as_ref() == "https") {
+                    "use `https://` instead".to_owned()
+                } else {
+                    format!("use one of: `{}`", join(&self.schemes))
+                };
+                Some(("link doesn't specify a scheme".into(), Some(help)))
+            });
+        }
+
+        let url = match Url::parse(url) {
+            Ok(u) => u,
+            Err(ParseError::RelativeUrlWithoutBase) => return None,
+            Err(e) => return Some((format!("link is not a valid URL: {}", e), None)),
+        };
+
+        let scheme = url.scheme();
+
+        if DANGEROUS.contains(&scheme) {
+            return Some((format!("`{}:` links are not allowed", scheme), None));
+        }
+
+        if !self
+            .schemes
+            .iter()
+            .any(|s| s.as_ref().eq_ignore_ascii_case(scheme))
+        {
+            let https = self.schemes.iter().any(|s| s.as_ref() == "https");
+
+            if scheme == "http" && https {
+                let help = "use `https://` instead".to_owned();
+                return Some(("link is not secure".into(), Some(help)));
+            }
+
+            let help = format!("use one of: `{}`", join(&self.schemes));
+            let label = format!("links with the `{}` scheme are not allowed", scheme);
+            return Some((label, Some(help)));
+        }
+
+        self.host_problem(&url)
+    }
+
+    /// What's wrong with the host of `url`, if anything, and how to fix it.
+    fn host_problem(&self, url: &Url) -> Option<(String, Option<String>)> {
+        let domain = match url.host() {
+            None => return None,
+            Some(Host::Domain(d)) => d.trim_end_matches('.').to_lowercase(),
+            Some(Host::Ipv4(_)) | Some(Host::Ipv6(_)) => {
+                let help = "link to a domain name instead".to_owned();
+                return Some(("link points to an IP address".into(), Some(help)));
+            }
+        };
+
+        if let Some(denied) = self.deny.iter().find(|d| matches(&domain, d.as_ref())) {
+            return Some((format!("links to `{}` are not allowed", denied), None));
+        }
+
+        if !self.allow.is_empty() && !self.allow.iter().any(|a| matches(&domain, a.as_ref())) {
+            let help = format!("link to one of: `{}`", join(&self.allow));
+            return Some((format!("links to `{}` are not allowed", domain), Some(help)));
+        }
+
+        None
+    }
+}
+
+impl<S> Lint for ExternalLinks<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        for node in ctx.body().descendants() {
+            let data = node.data.borrow();
+            let url = match &data.value {
+                NodeValue::Image(link) | NodeValue::Link(link) => &link.url,
+                _ => continue,
+            };
+
+            let (label, help) = match self.problem(&String::from_utf8_lossy(url)) {
+                Some(p) => p,
+                None => continue,
+            };
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&label),
+                }),
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, "linked here")],
+                footer: help
+                    .iter()
+                    .map(|h| Annotation {
+                        annotation_type: AnnotationType::Help,
+                        id: None,
+                        label: Some(h),
+                    })
+                    .collect(),
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let host = self
+            .allow
+            .first()
+            .map(ToString::to_string)
+            .unwrap_or_else(|| "example.com".into());
+
+        let mut examples = vec![Example::fail(
+            "---\nid: 1\n---\n\n[My profile](javascript:alert(1))\n",
+        )];
+
+        if self.schemes.iter().any(|s| s.as_ref() == "https") {
+            examples.insert(
+                0,
+                Example::pass(format!(
+                    "---\nid: 1\n---\n\n[My profile](https://{}/me)\n",
+                    host
+                )),
+            );
+        }
+
+        if let Some(denied) = self.deny.first() {
+            examples.push(Example::fail(format!(
+                "---\nid: 1\n---\n\n[My profile](https://{}/me)\n",
+                denied
+            )));
+        }
+
+        Metadata {
+            summary: "Links and images only point to permitted sites, over permitted schemes."
+                .into(),
+            rationale: "Voters follow these links, so they shouldn't run code, hide their \
+                destination, or be open to tampering."
+                .into(),
+            parameters: vec![
+                Parameter::new("schemes", &self.schemes),
+                Parameter::new("allow", &self.allow),
+                Parameter::new("deny", &self.deny),
+            ],
+            examples,
+        }
+    }
+}
+
+/// Whether `domain` is `pattern`, or a subdomain of it.
+fn matches(domain: &str, pattern: &str) -> bool {
+    let pattern = pattern.trim_end_matches('.').to_lowercase();
+
+    match domain.strip_suffix(&pattern) {
+        Some("") => true,
+        Some(prefix) => prefix.ends_with('.'),
+        None => false,
+    }
+}
+
+fn join<S>(items: &[S]) -> String
+where
+    S: AsRef<str>,
+{
+    items
+        .iter()
+        .map(AsRef::as_ref)
+        .collect::<Vec<_>>()
+        .join("`, `")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheme_relative() {
+        let lint = ExternalLinks {
+            schemes: vec!["https"],
+            allow: vec![],
+            deny: vec!["bit.ly"],
+        };
+
+        let label = |url| lint.problem(url).map(|(label, _)| label);
+
+        assert_eq!(
+            label("//bit.ly/abc").as_deref(),
+            Some("links to `bit.ly` are not allowed")
+        );
+        assert_eq!(
+            label("/\\bit.ly/abc").as_deref(),
+            Some("links to `bit.ly` are not allowed")
+        );
+        assert_eq!(
+            label("//127.0.0.1/abc").as_deref(),
+            Some("link points to an IP address")
+        );
+        assert_eq!(
+            label("//example.com/abc").as_deref(),
+            Some("link doesn't specify a scheme")
+        );
+        assert_eq!(label("/abc"), None);
+        assert_eq!(label("abc/def"), None);
+    }
+}
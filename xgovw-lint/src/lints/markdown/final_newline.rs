@@ -0,0 +1,73 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
+
+use super::lines;
+
+/// The file ends with exactly one newline.
+#[derive(Debug, Deserialize)]
+pub struct FinalNewline;
+
+impl Lint for FinalNewline {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let lines = lines::lines(ctx);
+
+        let last = match lines.iter().rposition(|line| !line.is_blank()) {
+            Some(l) => l,
+            None => return Ok(()),
+        };
+
+        if !ctx.body_source().ends_with('\n') && last == lines.len() - 1 {
+            let line = lines[last];
+            let end = line.text.chars().count();
+
+            return lines::report(
+                ctx,
+                slug,
+                "file must end with a newline",
+                &[(line, (end, end))],
+                "newline expected here",
+                "add a newline at the end of the file",
+            );
+        }
+
+        if last == lines.len() - 1 {
+            return Ok(());
+        }
+
+        // Empty lines at the end of a slice aren't shown, so point at the end
+        // of the last line instead.
+        let line = lines[last];
+        let end = line.text.chars().count();
+        let label = format!("followed by {} blank line(s)", lines.len() - last - 1);
+
+        lines::report(
+            ctx,
+            slug,
+            "file must end with exactly one newline",
+            &[(line, (end, end + 1))],
+            &label,
+            "remove the blank lines at the end of the file",
+        )
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "The file ends with exactly one newline.".into(),
+            rationale: "A missing or extra newline at the end of a file produces noisy diffs."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\nHello\n"),
+                Example::fail("---\nid: 1\n---\n\nHello"),
+                Example::fail("---\nid: 1\n---\n\nHello\n\n\n"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,308 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
+
+use comrak::nodes::AstNode;
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+use super::sections;
+
+/// The subsections allowed under one section.
+#[derive(Debug, Deserialize)]
+pub struct Section<S> {
+    pub name: S,
+    pub subsections: Vec<S>,
+}
+
+/// The body's headings form a well structured outline.
+///
+/// There are no level one headings (the application has no title of its own),
+/// levels aren't skipped, and no two sibling headings have the same name. The
+/// sections listed in `sections` can only contain the given level three
+/// subsections; other sections can contain anything.
+#[derive(Debug, Deserialize)]
+pub struct Headings<S> {
+    #[serde(default)]
+    pub sections: Vec<Section<S>>,
+}
+
+/// A heading's level, normalized name, and line.
+type Seen = (u32, String, u32);
+
+struct Problem {
+    label: String,
+    footer: Option<(AnnotationType, String)>,
+}
+
+impl Problem {
+    fn new(label: String) -> Self {
+        Self {
+            label,
+            footer: None,
+        }
+    }
+
+    fn with(mut self, annotation_type: AnnotationType, footer: String) -> Self {
+        self.footer = Some((annotation_type, footer));
+        self
+    }
+}
+
+impl<S> Headings<S>
+where
+    S: Display + AsRef<str>,
+{
+    /// Problems with the outline itself, for each heading that has any.
+    fn outline<'a>(&self, body: &'a AstNode<'a>) -> Vec<(&'a AstNode<'a>, Problem)> {
+        let mut problems = vec![];
+
+        // The headings that contain the current one, each with the levels,
+        // names, and lines of the children seen so far. The root is the
+        // document.
+        let mut parents: Vec<(u32, Vec<Seen>)> = vec![(1, vec![])];
+
+        for node in body.children() {
+            let level = match sections::level(node) {
+                Some(l) => l,
+                None => continue,
+            };
+
+            let line = node.data.borrow().start_line;
+            let (text, _) = sections::heading_text(node);
+
+            if level == 1 {
+                let problem = Problem::new("level 1 heading in the body".into()).with(
+                    AnnotationType::Help,
+                    "start sections with `##` instead".into(),
+                );
+                problems.push((node, problem));
+                continue;
+            }
+
+            while parents.len() > 1 && parents.last().unwrap().0 >= level {
+                parents.pop();
+            }
+
+            let (parent, siblings) = parents.last_mut().unwrap();
+
+            if level > *parent + 1 {
+                let problem = Problem::new(format!(
+                    "heading level skipped (`{}` after `{}`)",
+                    "#".repeat(level as usize),
+                    "#".repeat(*parent as usize)
+                ))
+                .with(
+                    AnnotationType::Help,
+                    format!("use `{}` instead", "#".repeat(*parent as usize + 1)),
+                );
+                problems.push((node, problem));
+            }
+
+            let key = text.trim().to_lowercase();
+
+            if let Some((_, _, first)) = siblings.iter().find(|(l, k, _)| *l == level && *k == key)
+            {
+                let problem = Problem::new(format!("duplicate heading `{}`", text.trim())).with(
+                    AnnotationType::Note,
+                    format!("`{}` was already used on line {}", text.trim(), first),
+                );
+                problems.push((node, problem));
+            } else {
+                siblings.push((level, key, line));
+            }
+
+            parents.push((level, vec![]));
+        }
+
+        problems
+    }
+
+    /// Subsections that aren't allowed in the section they're in.
+    fn subsections<'a>(&self, body: &'a AstNode<'a>) -> Vec<(&'a AstNode<'a>, Problem)> {
+        let names: Vec<_> = self.sections.iter().map(|s| s.name.as_ref()).collect();
+        let headings = sections::headings(body);
+        let matches = sections::match_sections(&headings, &names);
+
+        let mut problems = vec![];
+
+        for (heading, matched) in headings.iter().zip(matches) {
+            let section = match matched {
+                Some(m) => self
+                    .sections
+                    .iter()
+                    .find(|s| s.name.as_ref() == m.name)
+                    .unwrap(),
+                None => continue,
+            };
+
+            let children =
+                sections::content(heading.node).filter(|n| sections::level(n) == Some(3));
+
+            for child in children {
+                let (text, _) = sections::heading_text(child);
+                let text = text.trim();
+
+                if section
+                    .subsections
+                    .iter()
+                    .any(|s| s.as_ref().eq_ignore_ascii_case(text))
+                {
+                    continue;
+                }
+
+                let footer = match &section.subsections[..] {
+                    [] => format!("section `{}` can't have subsections", section.name),
+                    allowed => format!(
+                        "use one of: `{}`",
+                        allowed
+                            .iter()
+                            .map(AsRef::as_ref)
+                            .collect::<Vec<_>>()
+                            .join("`, `")
+                    ),
+                };
+
+                let problem = Problem::new(format!(
+                    "subsection `{}` is not allowed in `{}`",
+                    text, section.name
+                ))
+                .with(AnnotationType::Help, footer);
+
+                problems.push((child, problem));
+            }
+        }
+
+        problems
+    }
+}
+
+impl<S> Lint for Headings<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let mut problems = self.outline(ctx.body());
+        problems.extend(self.subsections(ctx.body()));
+        problems.sort_by_key(|(node, _)| node.data.borrow().start_line);
+
+        for (node, problem) in problems {
+            let data = node.data.borrow();
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&problem.label),
+                }),
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, "this heading")],
+                footer: problem
+                    .footer
+                    .iter()
+                    .map(|(annotation_type, label)| Annotation {
+                        annotation_type: *annotation_type,
+                        id: None,
+                        label: Some(label),
+                    })
+                    .collect(),
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let (section, subsection) = match self.sections.iter().find(|s| !s.subsections.is_empty()) {
+            Some(s) => (s.name.to_string(), s.subsections[0].to_string()),
+            None => ("Introduction".into(), "Background".into()),
+        };
+
+        Metadata {
+            summary: "The body's headings form a well structured outline.".into(),
+            rationale: "Reviewers navigate applications by their headings, and the website \
+                builds its table of contents from them."
+                .into(),
+            parameters: vec![Parameter::new("sections", &self.sections)],
+            examples: vec![
+                Example::pass(format!(
+                    "---\nid: 1\n---\n\n## {}\n\nText.\n\n### {}\n\nMore text.\n",
+                    section, subsection
+                )),
+                Example::fail(format!(
+                    "---\nid: 1\n---\n\n## {}\n\nText.\n\n#### {}\n\nMore text.\n",
+                    section, subsection
+                )),
+                Example::fail(format!(
+                    "---\nid: 1\n---\n\n## {0}\n\nText.\n\n## {0}\n\nMore text.\n",
+                    section
+                )),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use comrak::{parse_document, Arena, ComrakOptions};
+
+    fn problems(markdown: &str) -> Vec<(u32, String)> {
+        let lint = Headings {
+            sections: vec![Section {
+                name: "Introduction",
+                subsections: vec!["Background"],
+            }],
+        };
+
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &ComrakOptions::default());
+
+        let mut found = lint.outline(root);
+        found.extend(lint.subsections(root));
+        found
+            .into_iter()
+            .map(|(node, p)| (node.data.borrow().start_line, p.label))
+            .collect()
+    }
+
+    #[test]
+    fn outline() {
+        let found = problems(
+            "# Title\n\n## Introduction\n\n### Background\n\n#### Detail\n\n\
+             ## Budget\n\n#### Items\n\n### Items\n\n### items\n\n## Introduction\n",
+        );
+
+        assert_eq!(
+            found,
+            [
+                (1, "level 1 heading in the body".to_owned()),
+                (11, "heading level skipped (`####` after `##`)".to_owned()),
+                (15, "duplicate heading `items`".to_owned()),
+                (17, "duplicate heading `Introduction`".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn subsections() {
+        let found =
+            problems("## Introduction\n\n### Background\n\n### Goals\n\n## Budget\n\n### Goals\n");
+        assert_eq!(
+            found,
+            [(
+                5,
+                "subsection `Goals` is not allowed in `Introduction`".to_owned()
+            )]
+        );
+    }
+}
@@ -0,0 +1,360 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
+
+use comrak::nodes::{Ast, NodeHtmlBlock};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::tree::{self, Next, TraverseExt};
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+use std::ops::Range;
+
+/// Matches, in order of preference: a comment, a declaration or processing
+/// instruction, or a tag with its name and attributes.
+const MARKUP: &str = concat!(
+    r"(?s)(<!--.*?-->)",
+    r"|(<[!?][^>]*>)",
+    r"|<(/?)([A-Za-z][A-Za-z0-9\-]*)((?:[^>\x22']|\x22[^\x22]*\x22|'[^']*')*)>",
+);
+
+/// Matches an attribute's name, and its value (if any.)
+const ATTRIBUTE: &str = r#"([^\s"'>/=]+)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+)))?"#;
+
+/// Tags that are never allowed, since they run code or change the page.
+const TAGS: &[&str] = &[
+    "applet", "base", "embed", "frame", "frameset", "iframe", "link", "meta", "object", "script",
+    "style",
+];
+
+/// Raw HTML only uses the given `tags` and `attributes`.
+///
+/// Scripts, frames, and the like are never allowed, and neither are event
+/// handlers (`onclick`), inline styles, or `javascript:` URLs. Comments are
+/// always allowed.
+#[derive(Debug, Deserialize)]
+pub struct Html<S> {
+    pub tags: Vec<S>,
+    pub attributes: Vec<S>,
+}
+
+impl<S> Html<S>
+where
+    S: AsRef<str>,
+{
+    /// Finds the problems in `html`, with the byte range of each.
+    fn problems(&self, html: &str) -> Vec<(Range<usize>, String)> {
+        let markup = Regex::new(MARKUP).unwrap();
+        let attribute = Regex::new(ATTRIBUTE).unwrap();
+
+        let mut problems = vec![];
+
+        for captures in markup.captures_iter(html) {
+            // Comments are fine.
+            if captures.get(1).is_some() {
+                continue;
+            }
+
+            if let Some(declaration) = captures.get(2) {
+                let problem = "HTML declarations are not allowed".to_owned();
+                problems.push((declaration.range(), problem));
+                continue;
+            }
+
+            // Closing tags were already reported when they were opened.
+            if !captures[3].is_empty() {
+                continue;
+            }
+
+            let tag = captures.get(4).unwrap();
+            let name = tag.as_str().to_lowercase();
+
+            let allowed = !TAGS.contains(&name.as_str()) && self.allows(&self.tags, &name);
+
+            if !allowed {
+                let whole = captures.get(0).unwrap();
+                let problem = format!("HTML tag `<{}>` is not allowed", name);
+                problems.push((whole.range(), problem));
+            }
+
+            let attributes = captures.get(5).unwrap();
+
+            for attr in attribute.captures_iter(attributes.as_str()) {
+                let matched = attr.get(0).unwrap();
+                let range =
+                    attributes.start() + matched.start()..attributes.start() + matched.end();
+
+                let attr_name = attr[1].to_lowercase();
+                let value = attr
+                    .get(2)
+                    .or_else(|| attr.get(3))
+                    .or_else(|| attr.get(4))
+                    .map(|v| v.as_str())
+                    .unwrap_or_default();
+
+                let problem = if attr_name.starts_with("on") {
+                    format!("event handler `{}` is not allowed", attr_name)
+                } else if attr_name == "style" {
+                    "inline styles are not allowed".to_owned()
+                } else if is_javascript(value) {
+                    "`javascript:` URLs are not allowed".to_owned()
+                } else if allowed && !self.allows(&self.attributes, &attr_name) {
+                    format!("attribute `{}` is not allowed on `<{}>`", attr_name, name)
+                } else {
+                    continue;
+                };
+
+                problems.push((range, problem));
+            }
+        }
+
+        problems
+    }
+
+    fn allows(&self, list: &[S], name: &str) -> bool {
+        list.iter().any(|a| a.as_ref().eq_ignore_ascii_case(name))
+    }
+}
+
+impl<S> Lint for Html<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let mut visitor = Visitor {
+            ctx,
+            slug,
+            lint: self,
+        };
+
+        ctx.body().traverse().visit(&mut visitor)?;
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "Raw HTML only uses allowed tags and attributes.".into(),
+            rationale: "Applications are published on a public site, where scripts, frames, and \
+                styles could harm or mislead visitors."
+                .into(),
+            parameters: vec![
+                Parameter::new("tags", &self.tags),
+                Parameter::new("attributes", &self.attributes),
+            ],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\n<!-- reviewed: yes -->\n\nHello world.\n"),
+                Example::fail("---\nid: 1\n---\n\nHello world.\n\n<script>alert(1)</script>\n"),
+                Example::fail(
+                    "---\nid: 1\n---\n\nHello <span onclick=\"alert(1)\">world</span>.\n",
+                ),
+            ],
+        }
+    }
+}
+
+struct Visitor<'a, 'b, 'c, S> {
+    ctx: &'c Context<'a, 'b>,
+    slug: &'c str,
+    lint: &'c Html<S>,
+}
+
+impl<'a, 'b, 'c, S> Visitor<'a, 'b, 'c, S>
+where
+    S: AsRef<str>,
+{
+    fn check(&self, ast: &Ast, mut literal: &[u8]) -> Result<(), Error> {
+        // Blocks end with a newline that isn't part of their span.
+        while let Some(rest) = literal.strip_suffix(b"\n") {
+            literal = rest;
+        }
+
+        let html = String::from_utf8_lossy(literal);
+
+        for (range, problem) in self.lint.problems(&html) {
+            let slice = match self.ctx.text_span(ast, literal, range) {
+                Some(span) => self
+                    .ctx
+                    .span_slice(span, AnnotationType::Error, "not allowed"),
+                None => self
+                    .ctx
+                    .slice_for(ast, AnnotationType::Error, "not allowed"),
+            };
+
+            self.ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(self.slug),
+                    label: Some(&problem),
+                }),
+                slices: vec![slice],
+                footer: vec![],
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'c, S> tree::Visitor for Visitor<'a, 'b, 'c, S>
+where
+    S: AsRef<str>,
+{
+    type Error = Error;
+
+    fn enter_html_block(&mut self, ast: &Ast, block: &NodeHtmlBlock) -> Result<Next, Self::Error> {
+        self.check(ast, &block.literal)?;
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_html_inline(&mut self, ast: &Ast, html: &[u8]) -> Result<Next, Self::Error> {
+        self.check(ast, html)?;
+        Ok(Next::SkipChildren)
+    }
+}
+
+/// Named character references that can hide parts of a URL scheme.
+const ENTITIES: &[(&str, char)] = &[
+    ("Tab", '\t'),
+    ("NewLine", '\n'),
+    ("colon", ':'),
+    ("amp", '&'),
+    ("lt", '<'),
+    ("gt", '>'),
+    ("quot", '"'),
+    ("apos", '\''),
+    ("nbsp", '\u{a0}'),
+    ("lpar", '('),
+    ("rpar", ')'),
+    ("sol", '/'),
+    ("period", '.'),
+    ("semi", ';'),
+    ("excl", '!'),
+    ("num", '#'),
+    ("equals", '='),
+    ("commat", '@'),
+    ("percnt", '%'),
+];
+
+/// Decodes the character references (`&#106;`, `&#x6A;`, `&colon;`) in an
+/// attribute value. Like browsers, the trailing `;` is optional for numeric
+/// references.
+fn decode_entities(value: &str) -> String {
+    let mut decoded = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(amp) = rest.find('&') {
+        decoded.push_str(&rest[..amp]);
+        rest = &rest[amp + 1..];
+
+        if let Some(number) = rest.strip_prefix('#') {
+            let (digits, radix) = match number.strip_prefix(['x', 'X']) {
+                Some(hex) => (hex, 16),
+                None => (number, 10),
+            };
+
+            let len = digits
+                .find(|c: char| !c.is_digit(radix))
+                .unwrap_or(digits.len());
+
+            let c = u32::from_str_radix(&digits[..len], radix)
+                .ok()
+                .and_then(char::from_u32);
+
+            if let Some(c) = c {
+                decoded.push(c);
+                let after = &digits[len..];
+                rest = after.strip_prefix(';').unwrap_or(after);
+                continue;
+            }
+        } else if let Some((name, c)) = ENTITIES
+            .iter()
+            .find(|(name, _)| rest.starts_with(&format!("{};", name)))
+        {
+            decoded.push(*c);
+            rest = &rest[name.len() + 1..];
+            continue;
+        }
+
+        decoded.push('&');
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Whether an attribute value is a `javascript:` URL, ignoring the character
+/// references, whitespace, and case tricks browsers accept.
+fn is_javascript(value: &str) -> bool {
+    let compact: String = decode_entities(value)
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .flat_map(char::to_lowercase)
+        .collect();
+
+    compact.starts_with("javascript:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn problems_point_at_fragments() {
+        let lint = Html {
+            tags: vec!["a", "span"],
+            attributes: vec!["href"],
+        };
+
+        let html = "<!-- <script> --><a href=\"x\" title='y'><span style=\"a\">\
+            <iframe src=\"z\"></iframe><a href=\" JavaScript:alert(1)\">";
+
+        let found: Vec<_> = lint
+            .problems(html)
+            .into_iter()
+            .map(|(range, problem)| (&html[range], problem))
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                (
+                    "title='y'",
+                    "attribute `title` is not allowed on `<a>`".to_owned()
+                ),
+                ("style=\"a\"", "inline styles are not allowed".to_owned()),
+                (
+                    "<iframe src=\"z\">",
+                    "HTML tag `<iframe>` is not allowed".to_owned()
+                ),
+                (
+                    "href=\" JavaScript:alert(1)\"",
+                    "`javascript:` URLs are not allowed".to_owned()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn javascript_urls() {
+        assert!(is_javascript("javascript:alert(1)"));
+        assert!(is_javascript(" JaVaScRiPt:alert(1)"));
+        assert!(is_javascript("&#106;avascript:alert(1)"));
+        assert!(is_javascript("&#x6A;ava&#X53;cript&colon;alert(1)"));
+        assert!(is_javascript("&#0000106&#97vascript:alert(1)"));
+        assert!(is_javascript("java&Tab;script:alert(1)"));
+        assert!(is_javascript("jav&#x0A;ascript:alert(1)"));
+
+        assert!(!is_javascript("https://example.com/?a=1&b=javascript:"));
+        assert!(!is_javascript("&amp;javascript:"));
+    }
+}
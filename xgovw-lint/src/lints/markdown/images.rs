@@ -0,0 +1,488 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
+
+use comrak::nodes::{AstNode, NodeCode, NodeValue};
+
+use crate::lints::{Context, Error, Example, FetchContext, Lint, Metadata, Parameter};
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+use std::path::PathBuf;
+
+use super::link_targets::{self, Target};
+
+/// Image formats that can be recognized from their first few bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Png,
+    Jpeg,
+    Gif,
+    WebP,
+    Svg,
+}
+
+impl Format {
+    fn detect(bytes: &[u8]) -> Option<Self> {
+        if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if bytes.starts_with(b"\xff\xd8\xff") {
+            Some(Self::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(Self::Gif)
+        } else if bytes.len() >= 12 && bytes.starts_with(b"RIFF") && &bytes[8..12] == b"WEBP" {
+            Some(Self::WebP)
+        } else if svg_root(bytes).is_some() {
+            Some(Self::Svg)
+        } else {
+            None
+        }
+    }
+
+    /// The name used to configure the allowed formats.
+    fn name(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Jpeg => "jpeg",
+            Self::Gif => "gif",
+            Self::WebP => "webp",
+            Self::Svg => "svg",
+        }
+    }
+
+    /// Width and height in pixels, if they can be found.
+    fn dimensions(self, svg: &Svg, bytes: &[u8]) -> Option<(u64, u64)> {
+        match self {
+            Self::Png => png_dimensions(bytes),
+            Self::Jpeg => jpeg_dimensions(bytes),
+            Self::Gif => {
+                let b = bytes.get(6..10)?;
+                Some((le(&b[..2]), le(&b[2..])))
+            }
+            Self::WebP => webp_dimensions(bytes),
+            Self::Svg => svg.dimensions(bytes),
+        }
+    }
+}
+
+/// Images have alt text, and local images are small files in a permitted
+/// format.
+///
+/// Formats are detected from the file's contents (not its extension), and are
+/// named `png`, `jpeg`, `gif`, `webp`, and `svg`. SVGs must not contain
+/// scripts. Images are resolved like in `LinkTargets`, and ones outside the
+/// repository aren't read.
+#[derive(Debug, Deserialize)]
+pub struct Images<S> {
+    pub formats: Vec<S>,
+    #[serde(default)]
+    pub depth: usize,
+    pub max_bytes: Option<usize>,
+    pub max_width: Option<u64>,
+    pub max_height: Option<u64>,
+}
+
+impl<S> Images<S>
+where
+    S: Display + AsRef<str>,
+{
+    fn images<'a>(&self, body: &'a AstNode<'a>) -> Vec<(&'a AstNode<'a>, Option<PathBuf>)> {
+        body.descendants()
+            .filter_map(|node| match &node.data.borrow().value {
+                NodeValue::Image(link) => {
+                    let url = String::from_utf8_lossy(&link.url);
+                    let path = match link_targets::target(&url, self.depth) {
+                        Some(Target::File { path, .. }) => Some(path),
+                        _ => None,
+                    };
+                    Some((node, path))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// What's wrong with the image in `bytes`.
+    fn problems(&self, svg: &Svg, path: &str, bytes: &[u8]) -> Vec<String> {
+        let format = match Format::detect(bytes) {
+            Some(f) => f,
+            None => return vec![format!("`{}` is not a recognized image", path)],
+        };
+
+        let mut problems = vec![];
+
+        if !self.formats.iter().any(|f| f.as_ref() == format.name()) {
+            problems.push(format!(
+                "`{}` is a `{}` image, which is not allowed",
+                path,
+                format.name()
+            ));
+        }
+
+        if let Some(max) = self.max_bytes.filter(|m| bytes.len() > *m) {
+            problems.push(format!(
+                "`{}` is too large ({} bytes, max {})",
+                path,
+                bytes.len(),
+                max
+            ));
+        }
+
+        if let Some((width, height)) = format.dimensions(svg, bytes) {
+            if let Some(max) = self.max_width.filter(|m| width > *m) {
+                problems.push(format!(
+                    "`{}` is too wide ({} pixels, max {})",
+                    path, width, max
+                ));
+            }
+
+            if let Some(max) = self.max_height.filter(|m| height > *m) {
+                problems.push(format!(
+                    "`{}` is too tall ({} pixels, max {})",
+                    path, height, max
+                ));
+            }
+        }
+
+        if format == Format::Svg && svg.has_script(bytes) {
+            problems.push(format!("`{}` contains scripts", path));
+        }
+
+        problems
+    }
+}
+
+impl<S> Lint for Images<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn find_resources<'a>(&self, ctx: &FetchContext<'a>) -> Result<(), Error> {
+        for (_, path) in self.images(ctx.body()) {
+            if let Some(path) = path {
+                ctx.fetch_resource(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let svg = Svg::new();
+
+        for (node, path) in self.images(ctx.body()) {
+            let mut problems = vec![];
+
+            if alt_text(node).trim().is_empty() {
+                problems.push("image has no alt text".to_owned());
+            }
+
+            if let Some(path) = path {
+                match ctx.resource(&path)? {
+                    Ok(bytes) => {
+                        problems.extend(self.problems(&svg, &path.to_string_lossy(), bytes));
+                    }
+                    Err(e) => problems.push(link_targets::unreadable(&path, e)),
+                }
+            }
+
+            let data = node.data.borrow();
+
+            for problem in problems {
+                ctx.report(Snippet {
+                    title: Some(Annotation {
+                        annotation_type: AnnotationType::Error,
+                        id: Some(slug),
+                        label: Some(&problem),
+                    }),
+                    slices: vec![ctx.slice_for(&data, AnnotationType::Error, "image here")],
+                    footer: vec![],
+                    opt: Default::default(),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "Images have alt text, and local images are small and in a permitted \
+                format."
+                .into(),
+            rationale: "Alt text makes images accessible, and image files can carry scripts or \
+                be too large to review."
+                .into(),
+            parameters: vec![
+                Parameter::new("formats", &self.formats),
+                Parameter::new("depth", self.depth),
+                Parameter::new("max_bytes", self.max_bytes),
+                Parameter::new("max_width", self.max_width),
+                Parameter::new("max_height", self.max_height),
+            ],
+            // Examples can't include local images, since they aren't fetched.
+            examples: vec![
+                Example::pass(
+                    "---\nid: 1\n---\n\n![Our team at a hackathon](https://example.com/team.png)\n",
+                ),
+                Example::fail("---\nid: 1\n---\n\n![](https://example.com/team.png)\n"),
+            ],
+        }
+    }
+}
+
+fn alt_text<'a>(node: &'a AstNode<'a>) -> String {
+    let mut text = Vec::new();
+
+    for child in node.descendants().skip(1) {
+        match &child.data.borrow().value {
+            NodeValue::Text(v) | NodeValue::Code(NodeCode { literal: v, .. }) => {
+                text.extend_from_slice(v)
+            }
+            _ => (),
+        }
+    }
+
+    String::from_utf8_lossy(&text).into_owned()
+}
+
+fn be(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0, |acc, b| (acc << 8) | u64::from(*b))
+}
+
+fn le(bytes: &[u8]) -> u64 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |acc, b| (acc << 8) | u64::from(*b))
+}
+
+fn png_dimensions(bytes: &[u8]) -> Option<(u64, u64)> {
+    // The first chunk is always `IHDR`, which starts with the dimensions.
+    if bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+
+    Some((be(bytes.get(16..20)?), be(bytes.get(20..24)?)))
+}
+
+fn jpeg_dimensions(bytes: &[u8]) -> Option<(u64, u64)> {
+    let mut offset = 2;
+
+    loop {
+        if *bytes.get(offset)? != 0xff {
+            return None;
+        }
+
+        let marker = *bytes.get(offset + 1)?;
+
+        // Padding before a marker.
+        if marker == 0xff {
+            offset += 1;
+            continue;
+        }
+
+        let length = usize::try_from(be(bytes.get(offset + 2..offset + 4)?)).ok()?;
+
+        // Start of frame markers, except `DHT`, `JPG`, and `DAC`.
+        if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+            let height = be(bytes.get(offset + 5..offset + 7)?);
+            let width = be(bytes.get(offset + 7..offset + 9)?);
+            return Some((width, height));
+        }
+
+        offset += 2 + length;
+    }
+}
+
+fn webp_dimensions(bytes: &[u8]) -> Option<(u64, u64)> {
+    match bytes.get(12..16)? {
+        b"VP8 " => {
+            let width = le(bytes.get(26..28)?) & 0x3fff;
+            let height = le(bytes.get(28..30)?) & 0x3fff;
+            Some((width, height))
+        }
+        b"VP8L" => {
+            let bits = le(bytes.get(21..25)?);
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        b"VP8X" => {
+            let width = le(bytes.get(24..27)?) + 1;
+            let height = le(bytes.get(27..30)?) + 1;
+            Some((width, height))
+        }
+        _ => None,
+    }
+}
+
+/// The opening `<svg …>` tag, if `bytes` is an SVG document.
+fn svg_root(bytes: &[u8]) -> Option<&str> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let text = text.trim_start_matches('\u{feff}').trim_start();
+
+    if !["<?xml", "<svg", "<!--", "<!DOCTYPE"]
+        .iter()
+        .any(|p| text.starts_with(p))
+    {
+        return None;
+    }
+
+    let start = text.find("<svg")?;
+    let end = start + text[start..].find('>')?;
+    Some(&text[start..=end])
+}
+
+/// Patterns for reading SVG documents, compiled once per lint run.
+struct Svg {
+    width: Regex,
+    height: Regex,
+    viewbox: Regex,
+
+    /// Comments and CDATA sections, which can't contain tags.
+    unparsed: Regex,
+
+    /// Tags, with their name and attributes.
+    tag: Regex,
+
+    /// Attributes that run code.
+    handler: Regex,
+}
+
+impl Svg {
+    fn new() -> Self {
+        let attribute = |name: &str| {
+            Regex::new(&format!(
+                r#"\s{}\s*=\s*["']\s*([0-9.]+)\s*(?:px)?\s*["']"#,
+                name
+            ))
+            .unwrap()
+        };
+
+        Self {
+            width: attribute("width"),
+            height: attribute("height"),
+            viewbox: Regex::new(r#"\sviewBox\s*=\s*["']([^"']*)["']"#).unwrap(),
+            unparsed: Regex::new(r"(?s)<!--.*?-->|<!\[CDATA\[.*?\]\]>").unwrap(),
+            tag: Regex::new(r#"<([A-Za-z][\w:.\-]*)((?:"[^"]*"|'[^']*'|[^'">])*)>"#).unwrap(),
+            handler: Regex::new(r"(?i)(?:^|\s)on[a-z]+\s*=|javascript:").unwrap(),
+        }
+    }
+
+    fn dimensions(&self, bytes: &[u8]) -> Option<(u64, u64)> {
+        let root = svg_root(bytes)?;
+
+        let attribute = |re: &Regex| {
+            let value: f64 = re.captures(root)?[1].parse().ok()?;
+            Some(value.ceil() as u64)
+        };
+
+        let viewbox = || {
+            let captures = self.viewbox.captures(root)?;
+            let values: Vec<f64> = captures[1]
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|v| !v.is_empty())
+                .map(str::parse)
+                .collect::<Result<_, _>>()
+                .ok()?;
+
+            match values[..] {
+                [_, _, w, h] => Some((w.ceil() as u64, h.ceil() as u64)),
+                _ => None,
+            }
+        };
+
+        match (attribute(&self.width), attribute(&self.height)) {
+            (Some(w), Some(h)) => Some((w, h)),
+            _ => viewbox(),
+        }
+    }
+
+    /// Whether an SVG can run code when it's opened. Only tags are checked,
+    /// so text that happens to look like an event handler is fine.
+    fn has_script(&self, bytes: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(bytes);
+        let text = self.unparsed.replace_all(&text, "");
+
+        self.tag.captures_iter(&text).any(|tag| {
+            let name = tag[1].rsplit(':').next().unwrap_or_default();
+
+            name.eq_ignore_ascii_case("script")
+                || name.eq_ignore_ascii_case("foreignObject")
+                || self.handler.is_match(&tag[2])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimensions_from_headers() {
+        let patterns = Svg::new();
+
+        let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        png.extend_from_slice(&[0, 0, 2, 0, 0, 0, 1, 0]);
+        assert_eq!(Format::detect(&png), Some(Format::Png));
+        assert_eq!(Format::Png.dimensions(&patterns, &png), Some((512, 256)));
+
+        let jpeg = [
+            0xff, 0xd8, 0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, 0xff, 0xc0, 0x00, 0x11, 0x08, 0x00,
+            0x30, 0x00, 0x40,
+        ];
+        assert_eq!(Format::detect(&jpeg), Some(Format::Jpeg));
+        assert_eq!(Format::Jpeg.dimensions(&patterns, &jpeg), Some((64, 48)));
+
+        let mut webp = b"RIFF\0\0\0\0WEBPVP8X".to_vec();
+        webp.extend_from_slice(&[0; 8]);
+        webp.extend_from_slice(&[99, 0, 0, 49, 0, 0]);
+        assert_eq!(Format::detect(&webp), Some(Format::WebP));
+        assert_eq!(Format::WebP.dimensions(&patterns, &webp), Some((100, 50)));
+
+        let svg = br#"<?xml version="1.0"?><svg viewBox="0 0 30 20"></svg>"#;
+        assert_eq!(Format::detect(svg), Some(Format::Svg));
+        assert_eq!(Format::Svg.dimensions(&patterns, svg), Some((30, 20)));
+    }
+
+    #[test]
+    fn encoded_escapes_are_not_read() {
+        let arena = comrak::Arena::new();
+        let body = comrak::parse_document(
+            &arena,
+            "![a](%2e%2e/%2e%2e/secret.png) ![b](..%2f..%2fsecret.png) ![c](..\\\\..\\\\secret.png)\n",
+            &crate::comrak_options(),
+        );
+
+        let lint = Images {
+            formats: vec!["png"],
+            depth: 1,
+            max_bytes: None,
+            max_width: None,
+            max_height: None,
+        };
+
+        let paths: Vec<_> = lint.images(body).into_iter().map(|(_, p)| p).collect();
+        assert_eq!(paths, [None, None, None]);
+    }
+
+    #[test]
+    fn svg_scripts() {
+        let svg = Svg::new();
+
+        assert!(svg.has_script(br#"<svg><script>alert(1)</script></svg>"#));
+        assert!(svg.has_script(br#"<svg onload="alert(1)"></svg>"#));
+        assert!(svg.has_script(br#"<svg><a href="javascript:alert(1)">x</a></svg>"#));
+        assert!(svg.has_script(br#"<svg><svg:script/></svg>"#));
+        assert!(!svg.has_script(br#"<svg width="10" height="10"></svg>"#));
+        assert!(!svg.has_script(br#"<svg><desc>see on=the left</desc></svg>"#));
+        assert!(!svg.has_script(br#"<svg><desc><![CDATA[<a onclick=x>]]></desc></svg>"#));
+        assert!(!svg.has_script(br#"<svg><!-- <script> --></svg>"#));
+        assert!(!svg.has_script(br#"<svg><text title="a > b" font="x">on=1</text></svg>"#));
+    }
+}
@@ -0,0 +1,118 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct LineEndings;
+
+impl Lint for LineEndings {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let endings = ctx.line_endings();
+
+        if endings.has_bom() {
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some("file begins with a byte order mark (BOM)"),
+                }),
+                slices: vec![Slice {
+                    line_start: 1,
+                    fold: false,
+                    origin: ctx.origin(),
+                    source: ctx.line(1),
+                    annotations: vec![],
+                }],
+                footer: vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some("save the file as UTF-8 without a BOM"),
+                }],
+                opt: Default::default(),
+            })?;
+        }
+
+        // Carriage returns left over after normalization (or all of them, if
+        // normalization is turned off.)
+        let lone: Vec<_> = ctx
+            .source()
+            .split('\n')
+            .enumerate()
+            .filter(|(_, line)| line.contains('\r'))
+            .map(|(index, _)| index + 1)
+            .collect();
+
+        let mut lines: Vec<_> = endings.crlf_lines().iter().copied().chain(lone).collect();
+        lines.sort_unstable();
+
+        let first = match lines.first() {
+            Some(f) => *f,
+            None => return Ok(()),
+        };
+
+        let source = ctx.line(first.try_into().unwrap());
+        let source = source.trim_end_matches('\r');
+        let end = source.chars().count();
+
+        let note = format!("{} line(s) have non-LF line endings", lines.len());
+
+        ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(slug),
+                label: Some("file must use Unix-style line endings (LF)"),
+            }),
+            slices: vec![Slice {
+                line_start: first,
+                fold: false,
+                origin: ctx.origin(),
+                source,
+                annotations: vec![SourceAnnotation {
+                    annotation_type: AnnotationType::Error,
+                    label: "carriage return (CR) here",
+                    range: (end, end),
+                }],
+            }],
+            footer: vec![
+                Annotation {
+                    annotation_type: AnnotationType::Note,
+                    id: None,
+                    label: Some(&note),
+                },
+                Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some(
+                        "convert the file to LF (eg. `dos2unix`, or `git config core.autocrlf input`)",
+                    ),
+                },
+            ],
+            opt: Default::default(),
+        })?;
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "The file uses LF line endings and has no byte order mark.".into(),
+            rationale: "Windows-style line endings and byte order marks are invisible in most \
+                editors, but produce noisy diffs and confuse other tools."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\nHello\n"),
+                Example::fail("---\r\nid: 1\r\n---\r\n\r\nHello\r\n"),
+                Example::fail("\u{feff}---\nid: 1\n---\n\nHello\n"),
+            ],
+        }
+    }
+}
@@ -0,0 +1,104 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
+
+use super::lines::{self, Block};
+
+/// Lines in the body are at most `max` characters long.
+///
+/// Tables and code blocks aren't checked, and neither are lines that would be
+/// short enough without their URLs.
+#[derive(Debug, Deserialize)]
+pub struct LineLength {
+    pub max: usize,
+}
+
+impl LineLength {
+    /// Whether `text` is too long, not counting any URLs.
+    fn too_long(&self, text: &str) -> bool {
+        if text.chars().count() <= self.max {
+            return false;
+        }
+
+        let without: usize = text
+            .split(' ')
+            .filter(|word| !is_url(word))
+            .map(|word| word.chars().count() + 1)
+            .sum();
+
+        without.saturating_sub(1) > self.max
+    }
+}
+
+impl Lint for LineLength {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let found: Vec<_> = lines::lines(ctx)
+            .into_iter()
+            .filter(|line| line.block == Block::Text && self.too_long(line.text))
+            .map(|line| (line, (self.max, line.text.chars().count())))
+            .collect();
+
+        let title = format!("lines must be at most {} characters long", self.max);
+
+        lines::report(
+            ctx,
+            slug,
+            &title,
+            &found,
+            "too long",
+            "wrap the line (URLs, tables, and code blocks don't count)",
+        )
+    }
+
+    fn metadata(&self) -> Metadata {
+        let short = "Hello world. ".repeat(self.max / 26);
+        let long = "Hello world. ".repeat(self.max / 13 + 1);
+
+        Metadata {
+            summary: "Lines in the body aren't too long.".into(),
+            rationale: "Short lines are easier to review, and to comment on, in a diff.".into(),
+            parameters: vec![Parameter::new("max", self.max)],
+            examples: vec![
+                Example::pass(format!(
+                    "---\nid: 1\n---\n\n{}\n{}\n",
+                    short.trim(),
+                    short.trim()
+                )),
+                Example::pass(format!(
+                    "---\nid: 1\n---\n\n{} <https://example.com/{}>\n",
+                    short.trim(),
+                    "a".repeat(self.max)
+                )),
+                Example::fail(format!("---\nid: 1\n---\n\n{}\n", long.trim())),
+            ],
+        }
+    }
+}
+
+/// Whether `word` is (or contains) a URL, like `<https://example.com>` or
+/// `[text](https://example.com)`.
+fn is_url(word: &str) -> bool {
+    word.contains("://") || word.contains("mailto:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn urls_dont_count() {
+        let lint = LineLength { max: 20 };
+
+        assert!(!lint.too_long("Short enough."));
+        assert!(lint.too_long("This line is much too long."));
+        assert!(!lint.too_long("See <https://example.com/a/very/long/path>."));
+        assert!(!lint.too_long("See [docs](https://example.com/a/very/long/path)."));
+        assert!(lint.too_long("This is still too long, <https://example.com/a/very/long/path>."));
+    }
+}
@@ -0,0 +1,218 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! The lines of the body, with their line numbers and what kind of block
+//! they're part of.
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use comrak::nodes::NodeValue;
+
+use crate::lints::{Context, Error};
+
+/// What a tab is shown as in diagnostics.
+const TAB: &str = "    ";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Block {
+    /// Prose, headings, lists, and anything else that isn't one of the below.
+    Text,
+    Code,
+    Table,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Line<'a> {
+    /// The line number in the whole file, starting at one.
+    pub number: usize,
+
+    /// The line, without its newline.
+    pub text: &'a str,
+
+    /// Where the line starts in [`Context::body_source`].
+    pub offset: usize,
+
+    pub block: Block,
+}
+
+impl<'a> Line<'a> {
+    pub fn is_blank(&self) -> bool {
+        self.text.trim().is_empty()
+    }
+
+    /// The lines from `self` to `last` (inclusive) as one multi-line `Line`.
+    pub fn through(&self, source: &'a str, last: &Line<'a>) -> Line<'a> {
+        Line {
+            text: &source[self.offset..last.offset + last.text.len()],
+            ..*self
+        }
+    }
+}
+
+/// Splits [`Context::body_source`] into lines. A newline at the very end of
+/// the body doesn't start another line.
+pub(crate) fn lines<'a>(ctx: &Context<'a, '_>) -> Vec<Line<'a>> {
+    let first = usize::try_from(ctx.body().data.borrow().start_line).unwrap();
+    let source = ctx.body_source();
+
+    let mut texts: Vec<_> = source.split('\n').collect();
+    if source.is_empty() || source.ends_with('\n') {
+        texts.pop();
+    }
+
+    let mut offset = 0;
+    let mut lines: Vec<_> = texts
+        .into_iter()
+        .enumerate()
+        .map(|(index, text)| {
+            let line = Line {
+                number: first + index,
+                text,
+                offset,
+                block: Block::Text,
+            };
+            offset += text.len() + 1;
+            line
+        })
+        .collect();
+
+    for node in ctx.body().descendants() {
+        let data = node.data.borrow();
+
+        let (block, fallback) = match &data.value {
+            NodeValue::CodeBlock(code) => {
+                let mut len = code.literal.iter().filter(|c| **c == b'\n').count();
+                if code.fenced {
+                    len += 1;
+                }
+                (Block::Code, len)
+            }
+            NodeValue::Table(_) => (Block::Table, 0),
+            _ => continue,
+        };
+
+        let start = usize::try_from(data.start_line).unwrap();
+        let end = match ctx.span(&data) {
+            Some(span) if span.end().column() == 1 && span.end().line() > start => {
+                span.end().line() - 1
+            }
+            Some(span) => span.end().line(),
+            None => start + fallback,
+        };
+
+        for line in lines.iter_mut() {
+            if (start..=end).contains(&line.number) {
+                line.block = block;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Reports `title` with one slice for each of the given lines, underlining
+/// the character range of each. Tabs are shown as spaces, so the underlines
+/// line up.
+pub(crate) fn report(
+    ctx: &Context,
+    slug: &str,
+    title: &str,
+    found: &[(Line, (usize, usize))],
+    label: &str,
+    help: &str,
+) -> Result<(), Error> {
+    if found.is_empty() {
+        return Ok(());
+    }
+
+    let note = format!("found {} times", found.len());
+
+    let mut footer = vec![Annotation {
+        annotation_type: AnnotationType::Help,
+        id: None,
+        label: Some(help),
+    }];
+
+    if found.len() > 1 {
+        footer.insert(
+            0,
+            Annotation {
+                annotation_type: AnnotationType::Note,
+                id: None,
+                label: Some(&note),
+            },
+        );
+    }
+
+    let expanded: Vec<_> = found
+        .iter()
+        .map(|(line, range)| expand_tabs(line.text, *range))
+        .collect();
+
+    ctx.report(Snippet {
+        title: Some(Annotation {
+            annotation_type: AnnotationType::Error,
+            id: Some(slug),
+            label: Some(title),
+        }),
+        slices: found
+            .iter()
+            .zip(&expanded)
+            .map(|((line, _), (source, range))| Slice {
+                line_start: line.number,
+                fold: false,
+                origin: ctx.origin(),
+                source,
+                annotations: vec![SourceAnnotation {
+                    annotation_type: AnnotationType::Error,
+                    label,
+                    range: *range,
+                }],
+            })
+            .collect(),
+        footer,
+        opt: Default::default(),
+    })
+}
+
+/// Replaces each tab in `text` with spaces, and moves the character `range`
+/// to match.
+fn expand_tabs(text: &str, range: (usize, usize)) -> (String, (usize, usize)) {
+    let mut expanded = String::with_capacity(text.len());
+    let mut start = None;
+    let mut end = None;
+
+    for (index, c) in text.chars().enumerate() {
+        if index == range.0 {
+            start = Some(expanded.chars().count());
+        }
+        if index == range.1 {
+            end = Some(expanded.chars().count());
+        }
+
+        match c {
+            '\t' => expanded.push_str(TAB),
+            c => expanded.push(c),
+        }
+    }
+
+    let len = expanded.chars().count();
+    (expanded, (start.unwrap_or(len), end.unwrap_or(len)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tabs_are_expanded() {
+        assert_eq!(
+            expand_tabs("a\tb\t", (1, 2)),
+            ("a    b    ".to_owned(), (1, 5))
+        );
+        assert_eq!(expand_tabs("\tab", (3, 3)), ("    ab".to_owned(), (6, 6)));
+    }
+}
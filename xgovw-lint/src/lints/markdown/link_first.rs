@@ -8,24 +8,30 @@ use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
 use comrak::nodes::{Ast, NodeCode, NodeCodeBlock, NodeHtmlBlock, NodeLink};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 use crate::tree::{self, Next, TraverseExt};
 
+use serde::Deserialize;
+
 use ::regex::bytes::Regex as BytesRegex;
 
 use std::collections::HashSet;
+use std::fmt::{Debug, Display};
 
-#[derive(Debug)]
-pub struct LinkFirst<'n>(pub &'n str);
+#[derive(Debug, Deserialize)]
+pub struct LinkFirst<S>(pub S);
 
-impl<'n> Lint for LinkFirst<'n> {
+impl<S> Lint for LinkFirst<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let re = BytesRegex::new(self.0).map_err(Error::custom)?;
+        let re = BytesRegex::new(self.0.as_ref()).map_err(Error::custom)?;
 
         let mut visitor = Visitor {
             ctx,
             re,
-            pattern: self.0,
+            pattern: self.0.as_ref(),
             slug,
             linked: Default::default(),
             link_depth: 0,
@@ -35,6 +41,36 @@ impl<'n> Lint for LinkFirst<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let pattern = self.0.as_ref();
+        let mut examples = vec![];
+
+        // Examples need text that matches the pattern, which is only known
+        // when the pattern matches itself (eg. it has no special characters.)
+        if BytesRegex::new(pattern).map_or(false, |re| re.is_match(pattern.as_bytes())) {
+            examples.push(Example::pass(format!(
+                "---\nid: 1\n---\n\nThis builds on [{0}](https://example.com/), \
+                and {0} builds on others.\n",
+                pattern
+            )));
+            examples.push(Example::fail(format!(
+                "---\nid: 1\n---\n\nThis builds on {}.\n",
+                pattern
+            )));
+        }
+
+        Metadata {
+            summary: format!(
+                "The first mention of the pattern `{}` in the body is a link.",
+                self.0
+            ),
+            rationale: "Readers should be able to follow the first reference to another document."
+                .into(),
+            parameters: vec![Parameter::new("pattern", &self.0)],
+            examples,
+        }
+    }
 }
 
 struct Visitor<'a, 'b, 'c> {
@@ -55,22 +91,30 @@ impl<'a, 'b, 'c> Visitor<'a, 'b, 'c> {
 
             let footer_label = format!("the pattern in question: `{}`", self.pattern);
 
-            // TODO: Actually annotate the matches.
+            let source;
+            let slice = match self.ctx.text_span(ast, buf, matched.range()) {
+                Some(span) => self
+                    .ctx
+                    .span_slice(span, AnnotationType::Error, "link this"),
+                None => {
+                    source = self.ctx.source_for_text(ast.start_line, buf);
+                    Slice {
+                        fold: false,
+                        line_start: usize::try_from(ast.start_line).unwrap(),
+                        origin: self.ctx.origin(),
+                        source: &source,
+                        annotations: vec![],
+                    }
+                }
+            };
 
-            let source = self.ctx.source_for_text(ast.start_line, buf);
             self.ctx.report(Snippet {
                 title: Some(Annotation {
                     annotation_type: AnnotationType::Error,
                     id: Some(self.slug),
                     label: Some("the first match of the given pattern must be a link"),
                 }),
-                slices: vec![Slice {
-                    fold: false,
-                    line_start: usize::try_from(ast.start_line).unwrap(),
-                    origin: self.ctx.origin(),
-                    source: &source,
-                    annotations: vec![],
-                }],
+                slices: vec![slice],
                 footer: vec![Annotation {
                     id: None,
                     annotation_type: AnnotationType::Info,
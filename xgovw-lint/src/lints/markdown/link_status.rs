@@ -4,27 +4,33 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
 
 use comrak::nodes::{Ast, AstNode, NodeValue};
 
-use crate::lints::{Context, Error, FetchContext, Lint};
+use crate::lints::{Context, Error, Example, FetchContext, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
 
 use regex::bytes::Regex;
 
 use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display};
 use std::path::PathBuf;
 
-#[derive(Debug)]
-pub struct LinkStatus<'n> {
-    pub status: &'n str,
-    pub flow: &'n [&'n [&'n str]],
+#[derive(Debug, Deserialize)]
+pub struct LinkStatus<S> {
+    pub status: S,
+    pub flow: Vec<Vec<S>>,
 }
 
-impl<'n> LinkStatus<'n> {
+impl<S> LinkStatus<S>
+where
+    S: AsRef<str>,
+{
     fn tier(&self, map: &HashMap<&str, usize>, ctx: &Context<'_, '_>) -> usize {
         ctx.preamble()
-            .by_name(self.status)
+            .by_name(self.status.as_ref())
             .map(|f| f.value())
             .map(str::trim)
             .and_then(|s| map.get(s))
@@ -32,25 +38,26 @@ impl<'n> LinkStatus<'n> {
             .unwrap_or(0)
     }
 
-    fn find_links<'a>(node: &'a AstNode<'a>) -> impl 'a + Iterator<Item = (u32, PathBuf)> {
+    fn find_links<'a>(
+        node: &'a AstNode<'a>,
+    ) -> impl 'a + Iterator<Item = (&'a AstNode<'a>, PathBuf)> {
         let re = Regex::new("(?i)xGov-([0-9]+).md$").unwrap();
 
         node.descendants()
-            // Find all URLs and the lines they appear on.
+            // Find all URLs and the nodes they appear in.
             .filter_map(|start| match &*start.data.borrow() {
                 Ast {
                     value: NodeValue::Link(link),
-                    start_line,
                     ..
-                } => Some((*start_line, link.url.clone())),
+                } => Some((start, link.url.clone())),
                 _ => None,
             })
-            .filter_map(move |(start_line, url)| {
+            .filter_map(move |(start, url)| {
                 // This is a bit of a cheat, honestly. Doesn't correctly respect directories, but
                 // also doesn't allow directory traversal.
                 re.captures(&url).and_then(|c| {
                     Some((
-                        start_line,
+                        start,
                         format!(
                             "xgov-{}.md",
                             std::str::from_utf8(c.get(1).unwrap().as_bytes()).ok()?
@@ -62,7 +69,10 @@ impl<'n> LinkStatus<'n> {
     }
 }
 
-impl<'n> Lint for LinkStatus<'n> {
+impl<S> Lint for LinkStatus<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn find_resources<'a>(&self, ctx: &FetchContext<'a>) -> Result<(), Error> {
         Self::find_links(ctx.body())
             .map(|x| x.1)
@@ -76,16 +86,17 @@ impl<'n> Lint for LinkStatus<'n> {
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
         let mut map = HashMap::new();
         for (tier, values) in self.flow.iter().enumerate() {
-            for value in *values {
-                map.insert(*value, tier + 1);
+            for value in values {
+                map.insert(value.as_ref(), tier + 1);
             }
         }
 
         let my_tier = self.tier(&map, ctx);
         let mut min = usize::MAX;
 
-        for (start_line, url) in Self::find_links(ctx.body()) {
-            let xgov = match ctx.xgov(&url) {
+        for (node, url) in Self::find_links(ctx.body()) {
+            let data = node.data.borrow();
+            let xgov = match ctx.xgov(&url)? {
                 Ok(xgov) => xgov,
                 Err(e) => {
                     let label = format!("unable to read file `{}`: {}", url.display(), e);
@@ -95,13 +106,7 @@ impl<'n> Lint for LinkStatus<'n> {
                             label: Some(&label),
                             annotation_type: AnnotationType::Error,
                         }),
-                        slices: vec![Slice {
-                            fold: false,
-                            line_start: start_line.try_into().unwrap(),
-                            origin: ctx.origin(),
-                            source: ctx.line(start_line),
-                            annotations: vec![],
-                        }],
+                        slices: vec![ctx.slice_for(&data, AnnotationType::Error, "linked here")],
                         ..Default::default()
                     })?;
                     continue;
@@ -123,7 +128,7 @@ impl<'n> Lint for LinkStatus<'n> {
                 url.display(),
                 self.status,
                 ctx.preamble()
-                    .by_name(self.status)
+                    .by_name(self.status.as_ref())
                     .map(|f| f.value())
                     .unwrap_or("<missing>")
                     .trim(),
@@ -158,13 +163,7 @@ impl<'n> Lint for LinkStatus<'n> {
                     id: Some(slug),
                     label: Some(&label),
                 }),
-                slices: vec![Slice {
-                    fold: false,
-                    line_start: start_line.try_into().unwrap(),
-                    origin: ctx.origin(),
-                    source: ctx.line(start_line),
-                    annotations: vec![],
-                }],
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, "linked here")],
                 footer,
                 opt: Default::default(),
             })?;
@@ -172,4 +171,49 @@ impl<'n> Lint for LinkStatus<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |mine: &str, theirs: &str| {
+            let source = format!(
+                "---\n{0}: {1}\n---\n\nThis builds on [xGov-1](xgov-1.md).\n",
+                self.status, mine
+            );
+            let linked = format!("---\n{}: {}\n---\n", self.status, theirs);
+            (source, linked)
+        };
+
+        let first = self.flow.first().and_then(|t| t.first());
+        let last = self.flow.last().and_then(|t| t.first());
+
+        let mut examples = vec![];
+
+        if let (Some(first), Some(last), true) = (first, last, self.flow.len() >= 2) {
+            let (source, linked) = example(first.as_ref(), last.as_ref());
+            examples.push(
+                Example::pass(source)
+                    .with_origin("xgov-2.md")
+                    .with_file("xgov-1.md", linked),
+            );
+
+            let (source, linked) = example(last.as_ref(), first.as_ref());
+            examples.push(
+                Example::fail(source)
+                    .with_origin("xgov-2.md")
+                    .with_file("xgov-1.md", linked),
+            );
+        }
+
+        Metadata {
+            summary: format!(
+                "Linked proposals have a `{}` at least as advanced as this one.",
+                self.status
+            ),
+            rationale: "A proposal cannot be more stable than the proposals it builds on.".into(),
+            parameters: vec![
+                Parameter::new("status", &self.status),
+                Parameter::new("flow", &self.flow),
+            ],
+            examples,
+        }
+    }
 }
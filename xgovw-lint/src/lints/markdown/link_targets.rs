@@ -0,0 +1,347 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
+
+use comrak::nodes::{AstNode, NodeCode, NodeValue};
+use comrak::Arena;
+
+use crate::lints::{Context, Error, Example, FetchContext, Lint, Metadata, Parameter};
+use crate::preamble::Preamble;
+use crate::registry;
+
+use percent_encoding::percent_decode_str;
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Relative links and images point at files that exist, and their fragments
+/// (`#like-this`) point at headings.
+///
+/// Links are resolved against the document's directory, which is `depth`
+/// directories below the root of the repository. Links that would leave the
+/// repository are rejected without being read.
+#[derive(Debug, Deserialize)]
+pub struct LinkTargets {
+    #[serde(default)]
+    pub depth: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Target {
+    /// A heading in the same document.
+    Anchor(String),
+    File {
+        path: PathBuf,
+        fragment: Option<String>,
+    },
+    Escapes,
+}
+
+/// Where `url` points, or `None` if it isn't a relative link to a file or
+/// fragment. The document is `depth` directories below the repository root.
+pub(super) fn target(url: &str, depth: usize) -> Option<Target> {
+    let (rest, fragment) = match url.split_once('#') {
+        Some((r, f)) => (r, Some(decode(f))),
+        None => (url, None),
+    };
+
+    let path = rest.split('?').next().unwrap();
+
+    // Absolute paths and URLs (`/x`, `//host/x`, `https:x`) are left to
+    // `RelativeLinks`.
+    let scheme = path.find(':').filter(|i| !path[..*i].contains('/'));
+    if path.starts_with('/') || scheme.is_some() {
+        return None;
+    }
+
+    if path.is_empty() {
+        return fragment.map(Target::Anchor);
+    }
+
+    // Check the decoded path, so `%2e%2e` and `%2f` can't sneak past.
+    let decoded = decode(path);
+
+    // Directories can't be fetched, but are still rendered by forges.
+    if decoded.ends_with(['/', '\\']) {
+        return None;
+    }
+
+    // Absolute paths and drive letters only show up after decoding.
+    if decoded.starts_with(['/', '\\']) {
+        return Some(Target::Escapes);
+    }
+
+    let components: Vec<_> = decoded.split(['/', '\\']).collect();
+
+    if components[0].contains(':') {
+        return Some(Target::Escapes);
+    }
+
+    let mut level = depth;
+    for component in &components {
+        match *component {
+            "" | "." => (),
+            ".." if level == 0 => return Some(Target::Escapes),
+            ".." => level -= 1,
+            _ => level += 1,
+        }
+    }
+
+    Some(Target::File {
+        path: components.join("/").into(),
+        fragment,
+    })
+}
+
+impl LinkTargets {
+    fn targets<'a>(&self, body: &'a AstNode<'a>) -> Vec<(&'a AstNode<'a>, Target)> {
+        body.descendants()
+            .filter_map(|node| match &node.data.borrow().value {
+                NodeValue::Link(link) | NodeValue::Image(link) => {
+                    let url = String::from_utf8_lossy(&link.url);
+                    target(&url, self.depth).map(|t| (node, t))
+                }
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Lint for LinkTargets {
+    fn find_resources<'a>(&self, ctx: &FetchContext<'a>) -> Result<(), Error> {
+        for (_, target) in self.targets(ctx.body()) {
+            if let Target::File { path, .. } = target {
+                ctx.fetch_resource(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let own = anchors(ctx.body());
+
+        for (node, target) in self.targets(ctx.body()) {
+            let data = node.data.borrow();
+
+            let (label, suggestion) = match target {
+                Target::Escapes => ("link leaves the repository".to_owned(), None),
+                Target::Anchor(fragment) => match missing(&own, &fragment) {
+                    Some(s) => (
+                        format!("no heading for `#{}` in this document", fragment),
+                        s,
+                    ),
+                    None => continue,
+                },
+                Target::File { path, fragment } => match ctx.resource(&path)? {
+                    Err(e) => (unreadable(&path, e), None),
+                    Ok(content) => {
+                        let fragment = match fragment {
+                            Some(f) if is_markdown(&path) => f,
+                            _ => continue,
+                        };
+
+                        match missing(&file_anchors(content), &fragment) {
+                            Some(s) => (
+                                format!("no heading for `#{}` in `{}`", fragment, path.display()),
+                                s,
+                            ),
+                            None => continue,
+                        }
+                    }
+                },
+            };
+
+            let help = suggestion.map(|s| format!("did you mean `#{}`?", s));
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&label),
+                }),
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, "linked here")],
+                footer: help
+                    .iter()
+                    .map(|h| Annotation {
+                        annotation_type: AnnotationType::Help,
+                        id: None,
+                        label: Some(h),
+                    })
+                    .collect(),
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let escape = "../".repeat(self.depth + 1);
+
+        Metadata {
+            summary: "Relative links and images point at files and headings that exist.".into(),
+            rationale: "Broken links leave reviewers without the information they point to.".into(),
+            parameters: vec![Parameter::new("depth", self.depth)],
+            // Examples can't link to other files, since they aren't fetched.
+            examples: vec![
+                Example::pass(
+                    "---\nid: 1\n---\n\n## Relevant Experience\n\n\
+                    See [my experience](#relevant-experience).\n",
+                ),
+                Example::fail(
+                    "---\nid: 1\n---\n\n## Relevant Experience\n\n\
+                    See [my experience](#relevant-experiences).\n",
+                ),
+                Example::fail(format!(
+                    "---\nid: 1\n---\n\nSee [my keys]({}.ssh/id_ed25519).\n",
+                    escape
+                )),
+            ],
+        }
+    }
+}
+
+/// Describes why the file at `path` couldn't be read.
+pub(super) fn unreadable(path: &Path, error: &crate::Error) -> String {
+    match error {
+        crate::Error::Io { source, .. } if source.kind() == std::io::ErrorKind::NotFound => {
+            format!("`{}` does not exist", path.display())
+        }
+        crate::Error::Io { source, .. } => {
+            format!("unable to read `{}`: {}", path.display(), source)
+        }
+        e => format!("unable to read `{}`: {}", path.display(), e),
+    }
+}
+
+/// Returns `None` when `fragment` is one of `anchors`, otherwise the closest
+/// anchor (if any.)
+fn missing(anchors: &[String], fragment: &str) -> Option<Option<String>> {
+    if anchors.iter().any(|a| a == fragment) {
+        return None;
+    }
+
+    let closest = registry::suggest(fragment, anchors.iter().map(String::as_str));
+    Some(closest.map(ToOwned::to_owned))
+}
+
+fn decode(text: &str) -> String {
+    percent_decode_str(text).decode_utf8_lossy().into_owned()
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.eq_ignore_ascii_case("md") || e.eq_ignore_ascii_case("markdown"))
+        .unwrap_or(false)
+}
+
+/// The fragments that can be linked to in another markdown file, which may or
+/// may not have a preamble.
+fn file_anchors(content: &[u8]) -> Vec<String> {
+    let text = String::from_utf8_lossy(content);
+    let body = match Preamble::split(&text) {
+        Ok((_, body)) => body,
+        Err(_) => &text,
+    };
+
+    let arena = Arena::new();
+    anchors(comrak::parse_document(
+        &arena,
+        body,
+        &crate::comrak_options(),
+    ))
+}
+
+/// The fragments that can be linked to in `body`: the slug of every heading,
+/// as GitHub renders them, and any `id` or `name` attributes in raw HTML.
+fn anchors<'a>(body: &'a AstNode<'a>) -> Vec<String> {
+    let html = Regex::new(r#"\b(?:id|name)\s*=\s*["']([^"']+)["']"#).unwrap();
+
+    let mut seen = HashMap::new();
+    let mut anchors = vec![];
+
+    for node in body.descendants() {
+        match &node.data.borrow().value {
+            NodeValue::Heading(_) => (),
+            NodeValue::HtmlBlock(block) => {
+                let text = String::from_utf8_lossy(&block.literal);
+                anchors.extend(html.captures_iter(&text).map(|c| c[1].to_owned()));
+                continue;
+            }
+            NodeValue::HtmlInline(literal) => {
+                let text = String::from_utf8_lossy(literal);
+                anchors.extend(html.captures_iter(&text).map(|c| c[1].to_owned()));
+                continue;
+            }
+            _ => continue,
+        }
+
+        let mut text = Vec::new();
+        for child in node.descendants() {
+            match &child.data.borrow().value {
+                NodeValue::Text(v) | NodeValue::Code(NodeCode { literal: v, .. }) => {
+                    text.extend_from_slice(v)
+                }
+                _ => (),
+            }
+        }
+
+        let slug = slugify(&String::from_utf8_lossy(&text));
+
+        // Repeated headings get a numbered suffix.
+        let count = seen.entry(slug.clone()).or_insert(0);
+        match *count {
+            0 => anchors.push(slug),
+            n => anchors.push(format!("{}-{}", slug, n)),
+        }
+        *count += 1;
+    }
+
+    anchors
+}
+
+fn slugify(heading: &str) -> String {
+    heading
+        .trim()
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoded_escapes() {
+        assert_eq!(target("%2e%2e/%2e%2e/outside.md", 1), Some(Target::Escapes));
+        assert_eq!(target("..%2F..%2Foutside.md", 1), Some(Target::Escapes));
+        assert_eq!(target("..\\..\\outside.md", 1), Some(Target::Escapes));
+        assert_eq!(target("%2fetc/passwd", 1), Some(Target::Escapes));
+        assert_eq!(target("c%3a/secret.md", 1), Some(Target::Escapes));
+
+        assert_eq!(
+            target("%2e%2e/docs/a%20b.md#x", 1),
+            Some(Target::File {
+                path: "../docs/a b.md".into(),
+                fragment: Some("x".into()),
+            })
+        );
+    }
+}
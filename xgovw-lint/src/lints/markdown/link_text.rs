@@ -0,0 +1,215 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
+
+use comrak::nodes::{NodeCode, NodeValue};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use url::{Host, Url};
+
+/// Looks like a bare domain, optionally followed by a path, like
+/// `github.com/algorandfoundation`.
+const DOMAIN: &str = r"^(?:www\.)?[\p{L}\p{N}\-]+(?:\.[\p{L}\p{N}\-]+)*\.\p{L}{2,}(?:[/:?#]\S*)?$";
+
+/// File extensions that are also top level domains, so `README.md` isn't
+/// mistaken for a website.
+const EXTENSIONS: &[&str] = &[
+    "md", "pdf", "png", "jpg", "jpeg", "gif", "svg", "txt", "json", "rs", "py", "sh", "zip",
+    "toml", "yaml", "yml",
+];
+
+/// Links whose text looks like an address, but which point somewhere else.
+///
+/// Hosts are compared after converting internationalized names to punycode,
+/// and a subdomain counts as the same site. Destinations with a domain that
+/// mixes Latin and other letters in one label are always reported, since they
+/// usually imitate a well known name.
+#[derive(Debug, Deserialize)]
+pub struct LinkText;
+
+impl LinkText {
+    /// What's wrong with a link, if anything, and a note explaining it.
+    /// `domain` is `DOMAIN`, compiled.
+    fn problem(domain: &Regex, text: &str, url: &str) -> Option<(String, Option<String>)> {
+        let destination = Url::parse(url).ok()?;
+
+        let actual = match destination.host() {
+            Some(Host::Domain(d)) => normalize(d),
+            _ => return None,
+        };
+
+        let (unicode, _) = idna::domain_to_unicode(&actual);
+
+        if let Some(label) = unicode.split('.').find(|l| mixes_scripts(l)) {
+            let title = format!("link points to a lookalike domain `{}`", unicode);
+            let note = format!(
+                "`{}` mixes Latin letters with letters from another alphabet (`{}`)",
+                label, actual
+            );
+            return Some((title, Some(note)));
+        }
+
+        let shown = shown_host(domain, text)?;
+
+        if related(&shown, &actual) {
+            return None;
+        }
+
+        // Don't report `[report.pdf](https://example.com/report.pdf)`.
+        let last = destination.path_segments().and_then(Iterator::last);
+        if last == Some(text.trim()) {
+            return None;
+        }
+
+        let (shown_unicode, _) = idna::domain_to_unicode(&shown);
+        let title = format!(
+            "link text shows `{}`, but the link points to `{}`",
+            shown_unicode, unicode
+        );
+
+        let note = if unicode != actual || shown_unicode != shown {
+            Some(format!(
+                "these are `{}` and `{}` in punycode",
+                shown, actual
+            ))
+        } else {
+            None
+        };
+
+        Some((title, note))
+    }
+}
+
+impl Lint for LinkText {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let domain = Regex::new(DOMAIN).unwrap();
+
+        for node in ctx.body().descendants() {
+            let data = node.data.borrow();
+            let url = match &data.value {
+                NodeValue::Link(link) => String::from_utf8_lossy(&link.url),
+                _ => continue,
+            };
+
+            let mut text = Vec::new();
+            for child in node.descendants().skip(1) {
+                match &child.data.borrow().value {
+                    NodeValue::Text(v) | NodeValue::Code(NodeCode { literal: v, .. }) => {
+                        text.extend_from_slice(v)
+                    }
+                    _ => (),
+                }
+            }
+
+            let text = String::from_utf8_lossy(&text);
+            let (label, note) = match Self::problem(&domain, &text, &url) {
+                Some(p) => p,
+                None => continue,
+            };
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&label),
+                }),
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, "misleading link")],
+                footer: note
+                    .iter()
+                    .map(|n| Annotation {
+                        annotation_type: AnnotationType::Note,
+                        id: None,
+                        label: Some(n),
+                    })
+                    .collect(),
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "Links that look like addresses point to those addresses.".into(),
+            rationale: "Voters trust the address they can see, not the one hidden behind it."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass(
+                    "---\nid: 1\n---\n\n\
+                    [github.com/algorandfoundation](https://github.com/algorandfoundation)\n",
+                ),
+                Example::fail(
+                    "---\nid: 1\n---\n\n\
+                    [github.com/algorandfoundation](https://github.example/algorandfoundation)\n",
+                ),
+                Example::fail("---\nid: 1\n---\n\n[My profile](https://xn--gthub-n2e.com/me)\n"),
+            ],
+        }
+    }
+}
+
+/// The host the text of a link appears to be, in punycode, if it looks like
+/// an address at all. `domain` is `DOMAIN`, compiled.
+fn shown_host(domain: &Regex, text: &str) -> Option<String> {
+    let text = text.trim();
+
+    let url = if text.contains("://") {
+        Url::parse(text).ok()?
+    } else if domain.is_match(text) {
+        Url::parse(&format!("https://{}", text)).ok()?
+    } else {
+        return None;
+    };
+
+    let host = match url.host() {
+        Some(Host::Domain(d)) => normalize(d),
+        _ => return None,
+    };
+
+    // A bare file name, like `README.md`.
+    let labels: Vec<_> = host.split('.').collect();
+    if !text.contains('/') && labels.len() == 2 && EXTENSIONS.contains(&labels[1]) {
+        return None;
+    }
+
+    Some(host)
+}
+
+fn normalize(host: &str) -> String {
+    let host = host.trim_end_matches('.').to_lowercase();
+    match host.strip_prefix("www.") {
+        Some(h) => h.to_owned(),
+        None => host,
+    }
+}
+
+/// Whether one host is the other, or a subdomain of it.
+fn related(a: &str, b: &str) -> bool {
+    let sub = |x: &str, y: &str| x == y || x.ends_with(&format!(".{}", y));
+    sub(a, b) || sub(b, a)
+}
+
+/// Whether `label` has both Latin letters and letters from another script
+/// (like Cyrillic or Greek.)
+fn mixes_scripts(label: &str) -> bool {
+    let is_latin = |c: char| {
+        c.is_ascii_alphabetic()
+            || ('\u{c0}'..='\u{24f}').contains(&c)
+            || ('\u{1e00}'..='\u{1eff}').contains(&c)
+    };
+
+    let latin = label.chars().any(is_latin);
+    let other = label.chars().any(|c| c.is_alphabetic() && !is_latin(c));
+    latin && other
+}
@@ -0,0 +1,323 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use comrak::nodes::{Ast, NodeCode, NodeCodeBlock, NodeHtmlBlock};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::tree::{self, Next, TraverseExt};
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+use super::secrets::mask;
+use super::sections;
+
+const EMAIL: &str = r"[A-Za-z0-9._%+\-]+@[A-Za-z0-9.\-]+\.[A-Za-z]{2,}";
+
+/// Phone numbers start with a `+` country code or a parenthesized area code,
+/// so plain numbers, years, and amounts aren't mistaken for them.
+const PHONE: &str = concat!(
+    r"\+\d{1,3}(?:[\s.\-]?\(?\d{1,4}\)?){2,6}\b",
+    r"|\(\d{2,4}\)[\s.\-]?\d{3,4}[\s.\-]?\d{3,4}\b",
+);
+
+const STREET: &str = concat!(
+    r"\b\d{1,5}\s+(?:[A-Z][a-z]+\s+){1,3}",
+    r"(?:Street|St|Avenue|Ave|Road|Rd|Boulevard|Blvd|Lane|Ln|Drive|Dr|Court|Ct|Way|Place|Pl)\b",
+    r"|\b(?:Rue|Via|Calle|Avenida|Rua)\s+(?:[A-Za-z]+\s+){1,3}\d{1,5}\b",
+    r"|\b[A-Z][a-z]+(?:straße|strasse|weg|gasse|platz)\s+\d{1,5}\b",
+);
+
+/// Phone numbers have at least this many digits.
+const PHONE_DIGITS: usize = 9;
+
+/// Matches for `PHONE` that are IP addresses or numbers with thousands
+/// separators, not phone numbers.
+const NOT_PHONE: [&str; 2] = [
+    r"^\+?\d{1,3}(?:\.\d{1,3}){3}$",
+    r"^\+?\d{1,3}(?:[.,]\d{3})+$",
+];
+
+/// Whether a match for `PHONE` looks like a phone number. `not_phone` is
+/// `NOT_PHONE`, compiled.
+fn is_phone(text: &str, not_phone: &[Regex]) -> bool {
+    let digits = text.chars().filter(char::is_ascii_digit).count();
+    digits >= PHONE_DIGITS && !not_phone.iter().any(|re| re.is_match(text))
+}
+
+/// The kinds of personal information that are looked for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Email,
+    Phone,
+    Street,
+}
+
+impl Kind {
+    fn description(self) -> &'static str {
+        match self {
+            Self::Email => "an email address",
+            Self::Phone => "a phone number",
+            Self::Street => "a street address",
+        }
+    }
+}
+
+/// Personal information in the body: phone numbers, street addresses, and
+/// email addresses other than the one declared in the preamble.
+///
+/// Code isn't checked, and neither are the given `sections` (where contact
+/// details are expected) or any of the values in `allow`.
+#[derive(Debug, Deserialize)]
+pub struct Privacy<S> {
+    pub email: S,
+    #[serde(default)]
+    pub sections: Vec<S>,
+    #[serde(default)]
+    pub allow: Vec<S>,
+}
+
+impl<S> Lint for Privacy<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let names: Vec<_> = self.sections.iter().map(AsRef::as_ref).collect();
+        let headings = sections::headings(ctx.body());
+        let matches = sections::match_sections(&headings, &names);
+
+        // The line of each section heading, and whether the section is skipped.
+        let boundaries: Vec<_> = headings
+            .iter()
+            .zip(matches)
+            .map(|(h, m)| (h.line, m.is_some()))
+            .collect();
+
+        let mut allow: Vec<_> = self
+            .allow
+            .iter()
+            .map(|a| a.as_ref().to_lowercase())
+            .collect();
+
+        if let Some(field) = ctx.preamble().by_name(self.email.as_ref()) {
+            allow.push(field.value().trim().to_lowercase());
+        }
+
+        let mut visitor = Visitor {
+            ctx,
+            slug,
+            boundaries,
+            allow,
+            patterns: [
+                (Kind::Email, Regex::new(EMAIL).unwrap()),
+                (Kind::Phone, Regex::new(PHONE).unwrap()),
+                (Kind::Street, Regex::new(STREET).unwrap()),
+            ],
+            not_phone: NOT_PHONE.iter().map(|p| Regex::new(p).unwrap()).collect(),
+        };
+
+        ctx.body().traverse().visit(&mut visitor)?;
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let section = match self.sections.first() {
+            Some(s) => format!("\n## {}\n\nReach me at contact@example.com\n", s),
+            None => String::new(),
+        };
+
+        Metadata {
+            summary: "The body doesn't contain phone numbers, street addresses, or extra email \
+                addresses."
+                .into(),
+            rationale: "Applications are public, and personal details in them can be used for \
+                spam, fraud, or worse."
+                .into(),
+            parameters: vec![
+                Parameter::new("email", &self.email),
+                Parameter::new("sections", &self.sections),
+                Parameter::new("allow", &self.allow),
+            ],
+            examples: vec![
+                Example::pass(format!(
+                    "---\n{}: me@example.com\n---\n\nEmail me at me@example.com.\n{}",
+                    self.email, section
+                )),
+                Example::fail(format!(
+                    "---\n{}: me@example.com\n---\n\nCall me at +1 555 123 4567.\n",
+                    self.email
+                )),
+            ],
+        }
+    }
+}
+
+struct Visitor<'a, 'b, 'c> {
+    ctx: &'c Context<'a, 'b>,
+    slug: &'c str,
+    boundaries: Vec<(u32, bool)>,
+    allow: Vec<String>,
+    patterns: [(Kind, Regex); 3],
+    not_phone: Vec<Regex>,
+}
+
+impl<'a, 'b, 'c> Visitor<'a, 'b, 'c> {
+    fn skipped(&self, line: u32) -> bool {
+        self.boundaries
+            .iter()
+            .rev()
+            .find(|(start, _)| *start <= line)
+            .map(|(_, skip)| *skip)
+            .unwrap_or(false)
+    }
+
+    fn find<'t>(&self, text: &'t str) -> Vec<(Kind, regex::Match<'t>)> {
+        let mut found = vec![];
+
+        for (kind, re) in &self.patterns {
+            for matched in re.find_iter(text) {
+                if self.allow.contains(&matched.as_str().to_lowercase()) {
+                    continue;
+                }
+
+                if *kind == Kind::Phone && !is_phone(matched.as_str(), &self.not_phone) {
+                    continue;
+                }
+
+                found.push((*kind, matched));
+            }
+        }
+
+        found
+    }
+
+    fn report(
+        &self,
+        kind: Kind,
+        ast: &Ast,
+        txt: &[u8],
+        matched: regex::Match,
+    ) -> Result<(), Error> {
+        let title = format!("this looks like {}", kind.description());
+
+        let (line_start, source, range) = match self.ctx.text_span(ast, txt, matched.range()) {
+            Some(span) => {
+                let located = self.ctx.span_source(span);
+                (located.line_start, located.source, located.range)
+            }
+            None => {
+                let line = self.ctx.line(ast.start_line);
+                let start = usize::try_from(ast.start_line).unwrap();
+                (start, line, (0, line.chars().count()))
+            }
+        };
+
+        // Mask anything else on the same lines too.
+        let masked = self
+            .find(source)
+            .into_iter()
+            .map(|(_, m)| {
+                let start = source[..m.start()].chars().count();
+                (start, start + m.as_str().chars().count())
+            })
+            .fold(mask(source, range), |masked, r| mask(&masked, r));
+
+        self.ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Warning,
+                id: Some(self.slug),
+                label: Some(&title),
+            }),
+            slices: vec![Slice {
+                line_start,
+                fold: false,
+                origin: self.ctx.origin(),
+                source: &masked,
+                annotations: vec![SourceAnnotation {
+                    annotation_type: AnnotationType::Warning,
+                    label: "masked here",
+                    range,
+                }],
+            }],
+            footer: vec![Annotation {
+                annotation_type: AnnotationType::Help,
+                id: None,
+                label: Some("applications are public, so remove it unless it's meant to be"),
+            }],
+            opt: Default::default(),
+        })?;
+
+        Ok(())
+    }
+}
+
+impl<'a, 'b, 'c> tree::Visitor for Visitor<'a, 'b, 'c> {
+    type Error = Error;
+
+    fn enter_code(&mut self, _: &Ast, _: &NodeCode) -> Result<Next, Self::Error> {
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_code_block(&mut self, _: &Ast, _: &NodeCodeBlock) -> Result<Next, Self::Error> {
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_html_block(&mut self, _: &Ast, _: &NodeHtmlBlock) -> Result<Next, Self::Error> {
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_text(&mut self, ast: &Ast, txt: &[u8]) -> Result<Next, Self::Error> {
+        if self.skipped(ast.start_line) {
+            return Ok(Next::SkipChildren);
+        }
+
+        let text = String::from_utf8_lossy(txt);
+
+        for (kind, matched) in self.find(&text) {
+            self.report(kind, ast, txt, matched)?;
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phones(text: &str) -> Vec<&str> {
+        let not_phone: Vec<_> = NOT_PHONE.iter().map(|p| Regex::new(p).unwrap()).collect();
+
+        Regex::new(PHONE)
+            .unwrap()
+            .find_iter(text)
+            .map(|m| m.as_str())
+            .filter(|m| is_phone(m, &not_phone))
+            .collect()
+    }
+
+    #[test]
+    fn phone_numbers() {
+        assert_eq!(phones("Call +1 555 123 4567."), ["+1 555 123 4567"]);
+        assert_eq!(phones("Call (555) 123-4567."), ["(555) 123-4567"]);
+        assert_eq!(phones("Call +33 6 12 34 56 78."), ["+33 6 12 34 56 78"]);
+    }
+
+    #[test]
+    fn not_phone_numbers() {
+        assert!(phones("We ask for 100.000.000 ALGO.").is_empty());
+        assert!(phones("We ask for 1,000,000,000 ALGO.").is_empty());
+        assert!(phones("Grants in 2019 2020 2021.").is_empty());
+        assert!(phones("The node is at 192.168.100.200.").is_empty());
+        assert!(phones("Call 555 123 4567.").is_empty());
+    }
+}
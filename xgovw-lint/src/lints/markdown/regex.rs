@@ -8,12 +8,17 @@ use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
 use comrak::nodes::{Ast, NodeCode, NodeCodeBlock, NodeHtmlBlock, NodeLink};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 use crate::tree::{self, Next, TraverseExt};
 
+use serde::Deserialize;
+
 use ::regex::bytes::Regex as BytesRegex;
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Mode {
     /// Ensure that each syntax node individually doesn't contain the pattern.
@@ -22,23 +27,26 @@ pub enum Mode {
     //       matches the pattern.
 }
 
-#[derive(Debug)]
-pub struct Regex<'n> {
+#[derive(Debug, Deserialize)]
+pub struct Regex<S> {
     pub mode: Mode,
-    pub pattern: &'n str,
-    pub message: &'n str,
+    pub pattern: S,
+    pub message: S,
 }
 
-impl<'n> Lint for Regex<'n> {
+impl<S> Lint for Regex<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let re = BytesRegex::new(self.pattern).map_err(Error::custom)?;
+        let re = BytesRegex::new(self.pattern.as_ref()).map_err(Error::custom)?;
 
         let mut visitor = match self.mode {
             Mode::Excludes => ExcludesVisitor {
                 ctx,
                 re,
-                message: self.message,
-                pattern: self.pattern,
+                message: self.message.as_ref(),
+                pattern: self.pattern.as_ref(),
                 slug,
             },
         };
@@ -47,6 +55,41 @@ impl<'n> Lint for Regex<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let pattern = self.pattern.as_ref();
+        let example = |text: &str| format!("---\nid: 1\n---\n\n{}\n", text);
+
+        let mut examples = vec![];
+
+        // Failing examples need text that matches the pattern, which is only
+        // known when the pattern matches itself.
+        if let Ok(re) = BytesRegex::new(pattern) {
+            let text = "Some text.";
+            if !re.is_match(text.as_bytes()) {
+                examples.push(Example::pass(example(text)));
+            }
+
+            if re.is_match(pattern.as_bytes()) {
+                examples.push(Example::fail(example(pattern)));
+            }
+        }
+
+        Metadata {
+            summary: match self.mode {
+                Mode::Excludes => {
+                    format!("The body does not match the pattern `{}`.", self.pattern)
+                }
+            },
+            rationale: self.message.to_string(),
+            parameters: vec![
+                Parameter::new("mode", self.mode),
+                Parameter::new("pattern", &self.pattern),
+                Parameter::new("message", &self.message),
+            ],
+            examples,
+        }
+    }
 }
 
 struct ExcludesVisitor<'a, 'b, 'c> {
@@ -59,28 +102,37 @@ struct ExcludesVisitor<'a, 'b, 'c> {
 
 impl<'a, 'b, 'c> ExcludesVisitor<'a, 'b, 'c> {
     fn check(&self, ast: &Ast, buf: &[u8]) -> Result<Next, Error> {
-        if !self.re.is_match(buf) {
-            return Ok(Next::TraverseChildren);
-        }
+        let matched = match self.re.find(buf) {
+            Some(m) => m,
+            None => return Ok(Next::TraverseChildren),
+        };
 
         let footer_label = format!("the pattern in question: `{}`", self.pattern);
 
-        // TODO: Actually annotate the matches for `Mode::Excludes`.
+        let source;
+        let slice = match self.ctx.text_span(ast, buf, matched.range()) {
+            Some(span) => self
+                .ctx
+                .span_slice(span, AnnotationType::Error, "matched here"),
+            None => {
+                source = self.ctx.source_for_text(ast.start_line, buf);
+                Slice {
+                    fold: false,
+                    line_start: usize::try_from(ast.start_line).unwrap(),
+                    origin: self.ctx.origin(),
+                    source: &source,
+                    annotations: vec![],
+                }
+            }
+        };
 
-        let source = self.ctx.source_for_text(ast.start_line, buf);
         self.ctx.report(Snippet {
             title: Some(Annotation {
                 annotation_type: AnnotationType::Error,
                 id: Some(self.slug),
                 label: Some(self.message),
             }),
-            slices: vec![Slice {
-                fold: false,
-                line_start: usize::try_from(ast.start_line).unwrap(),
-                origin: self.ctx.origin(),
-                source: &source,
-                annotations: vec![],
-            }],
+            slices: vec![slice],
             footer: vec![Annotation {
                 id: None,
                 annotation_type: AnnotationType::Info,
@@ -4,41 +4,34 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
+use annotate_snippets::snippet::{Annotation, AnnotationType, Snippet};
 
-use comrak::nodes::{Ast, NodeValue};
+use comrak::nodes::NodeValue;
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
 
 use regex::bytes::Regex;
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
 pub struct RelativeLinks;
 
 impl Lint for RelativeLinks {
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
         let re = Regex::new("(^/)|(://)").unwrap();
 
-        let links = ctx
-            .body()
-            .descendants()
-            // Find all URLs and the lines they appear on.
-            .filter_map(|start| match &*start.data.borrow() {
-                Ast {
-                    value: NodeValue::Image(link),
-                    start_line,
-                    ..
-                } => Some((*start_line, link.url.clone())),
-                Ast {
-                    value: NodeValue::Link(link),
-                    start_line,
-                    ..
-                } => Some((*start_line, link.url.clone())),
-                _ => None,
-            })
-            .filter(|(_, url)| re.is_match(url));
-
-        for (line_start, _) in links {
+        for node in ctx.body().descendants() {
+            let data = node.data.borrow();
+            let url = match &data.value {
+                NodeValue::Image(link) | NodeValue::Link(link) => &link.url,
+                _ => continue,
+            };
+
+            if !re.is_match(url) {
+                continue;
+            }
+
             ctx.report(Snippet {
                 title: Some(Annotation {
                     id: Some(slug),
@@ -46,17 +39,28 @@ impl Lint for RelativeLinks {
                     label: Some("non-relative link or image"),
                 }),
                 footer: vec![],
-                slices: vec![Slice {
-                    line_start: usize::try_from(line_start).unwrap(),
-                    fold: false,
-                    origin: ctx.origin(),
-                    source: ctx.line(line_start),
-                    annotations: vec![],
-                }],
+                slices: vec![ctx.slice_for(
+                    &data,
+                    AnnotationType::Error,
+                    "use a relative path here",
+                )],
                 opt: Default::default(),
             })?;
         }
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "All links and images in the body are relative.".into(),
+            rationale: "Absolute URLs break when the repository is mirrored or rendered elsewhere."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\n[example](./example.md)\n"),
+                Example::fail("---\nid: 1\n---\n\n[example](https://example.com)\n"),
+            ],
+        }
+    }
 }
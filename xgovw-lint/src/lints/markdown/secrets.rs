@@ -0,0 +1,529 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use bip39::Language;
+
+use comrak::nodes::{Ast, NodeCode, NodeCodeBlock, NodeHeading, NodeHtmlBlock, NodeLink, NodeList};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+use crate::span::Span;
+use crate::tree::{self, Next, TraverseExt};
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use sha2::{Digest, Sha512_256};
+
+use std::ops::Range;
+
+/// Length of an Algorand account mnemonic, including the checksum word.
+const MNEMONIC_WORDS: usize = 25;
+
+/// Patterns for secrets other than mnemonics. When a pattern has a capture
+/// group, only the group is the secret.
+const PATTERNS: &[(&str, &str)] = &[
+    (
+        "an ed25519 private key",
+        r"(?:^|[^A-Za-z0-9+/=])([A-Za-z0-9+/]{86}==)(?:$|[^A-Za-z0-9+/=])",
+    ),
+    ("an ed25519 private key", r"\b[0-9a-fA-F]{128}\b"),
+    ("a private key", r"-----BEGIN [A-Z ]*PRIVATE KEY-----"),
+    (
+        "a GitHub token",
+        r"\b(?:gh[pousr]_[A-Za-z0-9]{36}|github_pat_[A-Za-z0-9_]{82})\b",
+    ),
+    ("an AWS access key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+    ("a Google API key", r"\bAIza[0-9A-Za-z_\-]{35}\b"),
+    ("a Slack token", r"\bxox[abprs]-[A-Za-z0-9\-]{10,}"),
+    ("a Stripe key", r"\b[rs]k_live_[A-Za-z0-9]{24,}\b"),
+];
+
+const MNEMONIC: &str = "an Algorand account mnemonic";
+
+/// Checks the checksum of an Algorand mnemonic, given the index of each word
+/// in the BIP-39 English wordlist.
+///
+/// The first 24 words are the 32 byte key packed into 11 bit groups (least
+/// significant bits first), and the last word is the first 11 bits of the
+/// SHA-512/256 hash of the key.
+fn is_mnemonic(indices: &[u16]) -> bool {
+    let (checksum, words) = match indices.split_last() {
+        Some(s) if indices.len() == MNEMONIC_WORDS => s,
+        _ => return false,
+    };
+
+    let mut key = Vec::with_capacity(33);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for word in words {
+        buffer |= u32::from(*word) << bits;
+        bits += 11;
+
+        while bits >= 8 {
+            key.push((buffer & 0xff) as u8);
+            buffer >>= 8;
+            bits -= 8;
+        }
+    }
+
+    if bits != 0 {
+        key.push((buffer & 0xff) as u8);
+    }
+
+    // 24 words hold 264 bits, so the last byte is padding.
+    if key.pop() != Some(0) {
+        return false;
+    }
+
+    let hash = Sha512_256::digest(&key);
+    let expected = (u16::from(hash[0]) | u16::from(hash[1]) << 8) & 0x7ff;
+
+    expected == *checksum
+}
+
+/// A word from the BIP-39 wordlist, and where it was found.
+#[derive(Debug, Clone)]
+struct Word<L> {
+    index: u16,
+    location: L,
+}
+
+/// Finds valid mnemonics in a run of words. Returns the range of words in each.
+fn find_mnemonics<L>(words: &[Word<L>]) -> Vec<Range<usize>> {
+    let indices: Vec<_> = words.iter().map(|w| w.index).collect();
+
+    let mut found = vec![];
+    let mut start = 0;
+
+    while start + MNEMONIC_WORDS <= indices.len() {
+        let end = start + MNEMONIC_WORDS;
+        if is_mnemonic(&indices[start..end]) {
+            found.push(start..end);
+            start = end;
+        } else {
+            start += 1;
+        }
+    }
+
+    found
+}
+
+/// Something that looks like a secret: what it is, and its byte range.
+#[derive(Debug, Clone)]
+struct Found {
+    what: &'static str,
+    range: Range<usize>,
+}
+
+struct Scanner {
+    patterns: Vec<(&'static str, Regex)>,
+    words: Regex,
+}
+
+impl Scanner {
+    fn new() -> Self {
+        Self {
+            patterns: PATTERNS
+                .iter()
+                .map(|(what, re)| (*what, Regex::new(re).unwrap()))
+                .collect(),
+            words: Regex::new(r"[A-Za-z]+|[^\sA-Za-z0-9.,;:()\-]+").unwrap(),
+        }
+    }
+
+    /// Splits `text` into words, along with their index in the BIP-39
+    /// wordlist. Punctuation and list numbering are skipped, but any other
+    /// symbol is returned as a word that isn't in the list.
+    fn tokens(&self, text: &str) -> Vec<(Option<u16>, Range<usize>)> {
+        self.words
+            .find_iter(text)
+            .map(|m| {
+                let word = m.as_str().to_lowercase();
+                (Language::English.find_word(&word), m.range())
+            })
+            .collect()
+    }
+
+    /// Splits `text` into runs of consecutive BIP-39 words long enough to
+    /// hold a mnemonic.
+    fn runs(&self, text: &str) -> Vec<Vec<Word<Range<usize>>>> {
+        let mut runs = vec![vec![]];
+
+        for (index, location) in self.tokens(text) {
+            match index {
+                Some(index) => runs.last_mut().unwrap().push(Word { index, location }),
+                None => runs.push(vec![]),
+            }
+        }
+
+        runs.retain(|r| r.len() >= MNEMONIC_WORDS);
+        runs
+    }
+
+    fn scan(&self, text: &str) -> Vec<Found> {
+        let mut found = vec![];
+
+        for (what, re) in &self.patterns {
+            for captures in re.captures_iter(text) {
+                let matched = captures.get(1).or_else(|| captures.get(0)).unwrap();
+                found.push(Found {
+                    what,
+                    range: matched.range(),
+                });
+            }
+        }
+
+        for run in self.runs(text) {
+            for range in find_mnemonics(&run) {
+                found.push(Found {
+                    what: MNEMONIC,
+                    range: run[range.start].location.start..run[range.end - 1].location.end,
+                });
+            }
+        }
+
+        found
+    }
+}
+
+/// Replaces the characters of `source` in `range` (a character range) with
+/// asterisks, keeping whitespace so the layout doesn't change.
+pub(super) fn mask(source: &str, range: (usize, usize)) -> String {
+    source
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if i >= range.0 && i < range.1 && !c.is_whitespace() {
+                '*'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+/// Private keys, mnemonics, and access tokens in the preamble or body.
+///
+/// The secrets themselves are masked in the diagnostics, so the report can be
+/// shared without leaking them again.
+#[derive(Debug, Deserialize)]
+pub struct Secrets;
+
+impl Lint for Secrets {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let scanner = Scanner::new();
+
+        for field in ctx.preamble().fields() {
+            let offset = field.name().len() + 1;
+            let source = field.source();
+
+            for found in scanner.scan(field.value()) {
+                let start = source[..offset + found.range.start].chars().count();
+                let end = source[..offset + found.range.end].chars().count();
+
+                report(
+                    ctx,
+                    slug,
+                    found.what,
+                    field.line_start(),
+                    source,
+                    (start, end),
+                )?;
+            }
+        }
+
+        let mut visitor = Visitor {
+            ctx,
+            slug,
+            scanner,
+            run: vec![],
+        };
+
+        ctx.body().traverse().visit(&mut visitor)?;
+        visitor.flush()?;
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let mnemonic = ["abandon"; MNEMONIC_WORDS - 1].join(" ") + " invest";
+
+        Metadata {
+            summary: "The application doesn't contain private keys, mnemonics, or access tokens."
+                .into(),
+            rationale: "Applications are public, and anyone who reads a secret can use it to \
+                take funds or impersonate the applicant."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\nMy address is in the preamble.\n"),
+                Example::fail(format!("---\nid: 1\n---\n\n```\n{}\n```\n", mnemonic)),
+                Example::fail(format!(
+                    "---\nid: 1\n---\n\nKey: ghp_{}\n",
+                    "a1B2c3D4e5".repeat(4).get(..36).unwrap()
+                )),
+            ],
+        }
+    }
+}
+
+fn report(
+    ctx: &Context,
+    slug: &str,
+    what: &str,
+    line_start: usize,
+    source: &str,
+    range: (usize, usize),
+) -> Result<(), Error> {
+    let title = format!("this looks like {}", what);
+    let masked = mask(source, range);
+
+    ctx.report(Snippet {
+        title: Some(Annotation {
+            annotation_type: AnnotationType::Error,
+            id: Some(slug),
+            label: Some(&title),
+        }),
+        slices: vec![Slice {
+            line_start,
+            fold: false,
+            origin: ctx.origin(),
+            source: &masked,
+            annotations: vec![SourceAnnotation {
+                annotation_type: AnnotationType::Error,
+                label: "masked here",
+                range,
+            }],
+        }],
+        footer: vec![
+            Annotation {
+                annotation_type: AnnotationType::Help,
+                id: None,
+                label: Some("remove it from the application"),
+            },
+            Annotation {
+                annotation_type: AnnotationType::Note,
+                id: None,
+                label: Some(
+                    "if it is real, treat it as compromised: move any funds and revoke the \
+                     secret, since it stays in the repository's history",
+                ),
+            },
+        ],
+        opt: Default::default(),
+    })?;
+
+    Ok(())
+}
+
+struct Visitor<'a, 'b, 'c> {
+    ctx: &'c Context<'a, 'b>,
+    slug: &'c str,
+    scanner: Scanner,
+
+    /// Words from consecutive text nodes in the current block, since a
+    /// mnemonic can be split over several lines.
+    run: Vec<Word<Option<Span>>>,
+}
+
+impl<'a, 'b, 'c> Visitor<'a, 'b, 'c> {
+    fn report_span(&self, what: &str, ast: &Ast, span: Option<Span>) -> Result<(), Error> {
+        match span {
+            Some(span) => {
+                let located = self.ctx.span_source(span);
+                report(
+                    self.ctx,
+                    self.slug,
+                    what,
+                    located.line_start,
+                    located.source,
+                    located.range,
+                )
+            }
+            None => {
+                let line = self.ctx.line(ast.start_line);
+                report(
+                    self.ctx,
+                    self.slug,
+                    what,
+                    usize::try_from(ast.start_line).unwrap(),
+                    line,
+                    (0, line.chars().count()),
+                )
+            }
+        }
+    }
+
+    /// Checks a node's literal text on its own.
+    fn check(&self, ast: &Ast, text: &[u8]) -> Result<Next, Error> {
+        let text = String::from_utf8_lossy(text);
+
+        for found in self.scanner.scan(&text) {
+            let span = self.ctx.text_span(ast, text.as_bytes(), found.range);
+            self.report_span(found.what, ast, span)?;
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+
+    /// Checks the words collected from the current block.
+    fn flush(&mut self) -> Result<(), Error> {
+        let run = std::mem::take(&mut self.run);
+
+        for range in find_mnemonics(&run) {
+            let span = match (run[range.start].location, run[range.end - 1].location) {
+                (Some(first), Some(last)) => Some(first.to(last)),
+                _ => None,
+            };
+
+            match span {
+                Some(span) => {
+                    let located = self.ctx.span_source(span);
+                    report(
+                        self.ctx,
+                        self.slug,
+                        MNEMONIC,
+                        located.line_start,
+                        located.source,
+                        located.range,
+                    )?;
+                }
+                None => {
+                    // Without spans there's no way to mask only the mnemonic,
+                    // so mask the whole body instead.
+                    let source = self.ctx.body_source();
+                    let line_start = self.ctx.body().data.borrow().start_line;
+                    report(
+                        self.ctx,
+                        self.slug,
+                        MNEMONIC,
+                        line_start.try_into().unwrap(),
+                        source,
+                        (0, source.chars().count()),
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn block(&mut self) -> Result<Next, Error> {
+        self.flush()?;
+        Ok(Next::TraverseChildren)
+    }
+}
+
+impl<'a, 'b, 'c> tree::Visitor for Visitor<'a, 'b, 'c> {
+    type Error = Error;
+
+    fn enter_paragraph(&mut self, _: &Ast) -> Result<Next, Self::Error> {
+        self.block()
+    }
+
+    fn enter_heading(&mut self, _: &Ast, _: &NodeHeading) -> Result<Next, Self::Error> {
+        self.block()
+    }
+
+    fn enter_item(&mut self, _: &Ast, _: &NodeList) -> Result<Next, Self::Error> {
+        self.block()
+    }
+
+    fn enter_table_cell(&mut self, _: &Ast) -> Result<Next, Self::Error> {
+        self.block()
+    }
+
+    fn enter_code_block(&mut self, ast: &Ast, block: &NodeCodeBlock) -> Result<Next, Self::Error> {
+        self.flush()?;
+        self.check(ast, &block.literal)
+    }
+
+    fn enter_html_block(&mut self, ast: &Ast, html: &NodeHtmlBlock) -> Result<Next, Self::Error> {
+        self.flush()?;
+        self.check(ast, &html.literal)
+    }
+
+    fn enter_code(&mut self, ast: &Ast, code: &NodeCode) -> Result<Next, Self::Error> {
+        self.check(ast, &code.literal)
+    }
+
+    fn enter_html_inline(&mut self, ast: &Ast, html: &[u8]) -> Result<Next, Self::Error> {
+        self.check(ast, html)
+    }
+
+    fn enter_link(&mut self, ast: &Ast, link: &NodeLink) -> Result<Next, Self::Error> {
+        self.check(ast, &link.url)?;
+        self.check(ast, &link.title)
+    }
+
+    fn enter_image(&mut self, ast: &Ast, link: &NodeLink) -> Result<Next, Self::Error> {
+        self.check(ast, &link.url)?;
+        self.check(ast, &link.title)
+    }
+
+    fn enter_text(&mut self, ast: &Ast, txt: &[u8]) -> Result<Next, Self::Error> {
+        let text = String::from_utf8_lossy(txt);
+
+        for found in self.scanner.scan(&text) {
+            // Mnemonics are found once the whole block has been seen.
+            if found.what != MNEMONIC {
+                let span = self.ctx.text_span(ast, txt, found.range);
+                self.report_span(found.what, ast, span)?;
+            }
+        }
+
+        for (index, range) in self.scanner.tokens(&text) {
+            match index {
+                Some(index) => self.run.push(Word {
+                    index,
+                    location: self.ctx.text_span(ast, txt, range),
+                }),
+                None => self.flush()?,
+            }
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indices(mnemonic: &str) -> Vec<u16> {
+        mnemonic
+            .split(' ')
+            .map(|w| Language::English.find_word(w).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn zero_key_mnemonic() {
+        let mnemonic = ["abandon"; 24].join(" ") + " invest";
+        assert!(is_mnemonic(&indices(&mnemonic)));
+    }
+
+    #[test]
+    fn bad_checksum() {
+        let mnemonic = ["abandon"; 24].join(" ") + " abandon";
+        assert!(!is_mnemonic(&indices(&mnemonic)));
+    }
+
+    #[test]
+    fn scan_masks_only_the_secret() {
+        let text = format!("1. {}\n25. invest", ["abandon"; 24].join(" "));
+        let found = Scanner::new().scan(&text);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].what, MNEMONIC);
+        assert_eq!(&text[found[0].range.clone()], &text[3..]);
+
+        assert_eq!(mask("key: abc def", (5, 12)), "key: *** ***");
+    }
+}
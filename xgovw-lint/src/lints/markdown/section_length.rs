@@ -0,0 +1,198 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
+
+use comrak::nodes::{Ast, AstNode, NodeHeading, NodeValue};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+use super::sections;
+
+/// Bounds on the amount of plain text in a section, or in the whole body when
+/// `section` is `None`.
+///
+/// Headings, formatting, and raw HTML don't count towards the length.
+#[derive(Debug, Deserialize)]
+pub struct SectionLength<S> {
+    pub section: Option<S>,
+    pub min_words: Option<usize>,
+    pub max_words: Option<usize>,
+    pub min_chars: Option<usize>,
+    pub max_chars: Option<usize>,
+}
+
+impl<S> SectionLength<S>
+where
+    S: Display,
+{
+    /// Returns a short description of each bound `text` violates, and the
+    /// details of the violation.
+    fn problems(&self, text: &str) -> Vec<(&'static str, String)> {
+        let words = text.split_whitespace().count();
+        let chars = text.chars().count();
+
+        let mut problems = vec![];
+
+        if let Some(min) = self.min_words.filter(|m| words < *m) {
+            problems.push(("too short", format!("{} words, min {}", words, min)));
+        }
+
+        if let Some(max) = self.max_words.filter(|m| words > *m) {
+            problems.push(("too long", format!("{} words, max {}", words, max)));
+        }
+
+        if let Some(min) = self.min_chars.filter(|m| chars < *m) {
+            problems.push(("too short", format!("{} characters, min {}", chars, min)));
+        }
+
+        if let Some(max) = self.max_chars.filter(|m| chars > *m) {
+            problems.push(("too long", format!("{} characters, max {}", chars, max)));
+        }
+
+        problems
+    }
+
+    fn lint_body<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let blocks = ctx.body().children().filter(|n| !is_section_heading(n));
+        let text = sections::plain_text(blocks);
+
+        for (problem, details) in self.problems(&text) {
+            let label = format!("{} is {} ({})", self.subject(), problem, details);
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&label),
+                }),
+                slices: vec![Slice {
+                    fold: true,
+                    annotations: vec![],
+                    origin: ctx.origin(),
+                    source: ctx.body_source(),
+                    line_start: ctx.body().data.borrow().start_line.try_into().unwrap(),
+                }],
+                footer: vec![],
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn subject(&self) -> String {
+        match &self.section {
+            Some(s) => format!("section `{}`", s),
+            None => "body".into(),
+        }
+    }
+}
+
+impl<S> Lint for SectionLength<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let name = match &self.section {
+            Some(s) => s.as_ref(),
+            None => return self.lint_body(slug, ctx),
+        };
+
+        let headings = sections::headings(ctx.body());
+        let matches = sections::match_sections(&headings, &[name]);
+
+        // Missing sections are left for `SectionRequired`.
+        for (heading, _) in headings.iter().zip(matches).filter(|(_, m)| m.is_some()) {
+            let text = sections::plain_text(sections::content(heading.node));
+
+            for (problem, details) in self.problems(&text) {
+                let label = format!("{} is {} ({})", self.subject(), problem, details);
+                let data = heading.node.data.borrow();
+
+                ctx.report(Snippet {
+                    title: Some(Annotation {
+                        annotation_type: AnnotationType::Error,
+                        id: Some(slug),
+                        label: Some(&label),
+                    }),
+                    slices: vec![ctx.slice_for(&data, AnnotationType::Error, problem)],
+                    footer: vec![],
+                    opt: Default::default(),
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let example = |words: usize| {
+            let text = vec!["word"; words].join(" ");
+            match &self.section {
+                Some(s) => format!("---\nid: 1\n---\n\n## {}\n\n{}\n", s, text),
+                None => format!("---\nid: 1\n---\n\n{}\n", text),
+            }
+        };
+
+        // Each "word" is four characters, plus a space between them.
+        let fits = |words: usize| self.problems(&vec!["word"; words].join(" ")).is_empty();
+
+        let mut examples = vec![];
+
+        let enough = self
+            .min_words
+            .unwrap_or(1)
+            .max(self.min_chars.map(|c| (c + 1) / 5 + 1).unwrap_or(1));
+
+        if fits(enough) {
+            examples.push(Example::pass(example(enough)));
+        }
+
+        let too_many = match (self.max_words, self.max_chars) {
+            (Some(max), _) => Some(max + 1),
+            (None, Some(max)) => Some((max + 1) / 5 + 1),
+            (None, None) => None,
+        };
+
+        let too_few = match (self.min_words, self.min_chars) {
+            (Some(min), _) if min > 0 => Some(min - 1),
+            (_, Some(min)) if min > 0 => Some(0),
+            _ => None,
+        };
+
+        if let Some(words) = too_many.or(too_few) {
+            examples.push(Example::fail(example(words)));
+        }
+
+        Metadata {
+            summary: format!("The {} has an acceptable length.", self.subject()),
+            rationale: "Blank sections and walls of text are both hard to review fairly.".into(),
+            parameters: vec![
+                Parameter::new("section", &self.section),
+                Parameter::new("min_words", self.min_words),
+                Parameter::new("max_words", self.max_words),
+                Parameter::new("min_chars", self.min_chars),
+                Parameter::new("max_chars", self.max_chars),
+            ],
+            examples,
+        }
+    }
+}
+
+fn is_section_heading<'a>(node: &'a AstNode<'a>) -> bool {
+    matches!(
+        &*node.data.borrow(),
+        Ast {
+            value: NodeValue::Heading(NodeHeading { level: 2, .. }),
+            ..
+        }
+    )
+}
@@ -6,25 +6,31 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
-use comrak::nodes::{Ast, NodeHeading, NodeValue};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-use crate::lints::{Context, Error, Lint};
+use super::sections::{self, Match};
+
+use serde::Deserialize;
 
 use std::collections::HashMap;
-use std::fmt::Write;
+use std::fmt::{Debug, Display, Write};
 
-#[derive(Debug)]
-pub struct SectionOrder<'n>(pub &'n [&'n str]);
+#[derive(Debug, Deserialize)]
+pub struct SectionOrder<S>(pub Vec<S>);
 
-impl<'n> SectionOrder<'n> {
+impl<S> SectionOrder<S>
+where
+    S: AsRef<str>,
+{
     fn find_preceding(&self, present: &[&str], needle: &str) -> Option<&str> {
-        let needle_idx = match self.0.iter().position(|x| *x == needle) {
+        let needle_idx = match self.0.iter().position(|x| x.as_ref() == needle) {
             None | Some(0) => return None,
             Some(i) => i,
         };
 
         for (idx, name) in self.0.iter().enumerate().rev() {
-            if *name != needle && present.contains(name) && idx < needle_idx {
+            let name = name.as_ref();
+            if name != needle && present.contains(&name) && idx < needle_idx {
                 return Some(name);
             }
         }
@@ -33,55 +39,55 @@ impl<'n> SectionOrder<'n> {
     }
 }
 
-impl<'n> Lint for SectionOrder<'n> {
+impl<S> Lint for SectionOrder<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        // Collect the headings.
-        let headings_bytes = ctx
-            .body()
-            .descendants()
-            // Find all headings of level 2.
-            .filter_map(|start| match &*start.data.borrow() {
-                Ast {
-                    value: NodeValue::Heading(NodeHeading { level: 2, .. }),
-                    start_line,
-                    ..
-                } => Some((*start_line, start)),
-                _ => None,
-            })
-            // Descend into their children.
-            .map(|(start_line, heading)| {
-                let collected: Vec<_> = heading
-                    .descendants()
-                    .skip(1)
-                    // Filter for text nodes.
-                    .filter_map(|child| match &*child.data.borrow() {
-                        Ast {
-                            value: NodeValue::Text(v),
-                            ..
-                        } => Some(v.to_vec()),
-                        _ => None,
-                    })
-                    .flatten()
-                    .collect();
-                (start_line, collected)
-            });
-
-        let mut headings = Vec::new();
-        for (line_start, bytes) in headings_bytes {
-            headings.push((line_start, String::from_utf8(bytes)?));
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+        let headings = sections::headings(ctx.body());
+
+        // Near misses count as the section they resemble when checking the
+        // order, but the heading still has to match the expected name.
+        let matches = sections::match_sections(&headings, &names);
+
+        for (heading, matched) in headings.iter().zip(&matches) {
+            let name = match matched {
+                Some(Match { exact: false, name }) => name,
+                _ => continue,
+            };
+
+            let annotation = if heading.text == *name {
+                "remove the formatting".to_owned()
+            } else {
+                format!("did you mean `{}`?", name)
+            };
+            let title = format!("heading for section `{}` doesn't match its name", name);
+            let help = format!("change the heading to `## {}`", name);
+
+            let data = heading.node.data.borrow();
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    id: Some(slug),
+                    annotation_type: AnnotationType::Error,
+                    label: Some(&title),
+                }),
+                footer: vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some(&help),
+                }],
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, &annotation)],
+                opt: Default::default(),
+            })?;
         }
 
         // Check for unknown sections.
         let unknowns: Vec<_> = headings
             .iter()
-            .filter(|(_, f)| !self.0.contains(&f.as_str()))
-            .map(|(line_start, _)| Slice {
-                line_start: usize::try_from(*line_start).unwrap(),
-                fold: false,
-                origin: ctx.origin(),
-                source: ctx.line(*line_start),
-                annotations: vec![],
-            })
+            .zip(&matches)
+            .filter(|(_, m)| m.is_none())
+            .map(|(heading, _)| heading.node.data.borrow())
             .collect();
 
         if !unknowns.is_empty() {
@@ -92,18 +98,26 @@ impl<'n> Lint for SectionOrder<'n> {
                     label: Some("body has extra section(s)"),
                 }),
                 footer: vec![],
-                slices: unknowns,
+                slices: unknowns
+                    .iter()
+                    .map(|data| ctx.slice_for(data, AnnotationType::Error, "unrecognized section"))
+                    .collect(),
                 opt: Default::default(),
             })?;
         }
 
         // Check that sections are in the correct order.
-        let map: HashMap<_, _> = headings.into_iter().map(|(a, b)| (b, a)).collect();
-        let present: Vec<_> = map.keys().map(String::as_str).collect();
+        let map: HashMap<_, _> = headings
+            .iter()
+            .zip(&matches)
+            .filter_map(|(heading, m)| m.map(|m| (m.name, heading.line)))
+            .collect();
+        let present: Vec<_> = map.keys().copied().collect();
 
         let mut max_line = 0;
         for name in self.0.iter() {
-            if let Some(line_start) = map.get(*name).copied() {
+            let name = name.as_ref();
+            if let Some(line_start) = map.get(name).copied() {
                 let cur = max_line;
                 max_line = line_start;
 
@@ -146,4 +160,42 @@ impl<'n> Lint for SectionOrder<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |names: &[&str]| {
+            let sections: String = names
+                .iter()
+                .map(|n| format!("\n## {}\n\nText.\n", n))
+                .collect();
+            format!("---\nid: 1\n---\n{}", sections)
+        };
+
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        let mut examples = vec![Example::pass(example(&names))];
+
+        if names.len() >= 2 {
+            let mut swapped = names.clone();
+            swapped.swap(0, 1);
+            examples.push(Example::fail(example(&swapped)));
+        }
+
+        if let Some((last, rest)) = names.split_last() {
+            let lower = last.to_lowercase();
+            if lower != *last {
+                let mut misspelled = rest.to_vec();
+                misspelled.push(&lower);
+                examples.push(Example::fail(example(&misspelled)));
+            }
+        }
+
+        Metadata {
+            summary: "There are no extra sections and the sections are in the correct order."
+                .into(),
+            rationale: "A consistent outline makes applications easy to compare side by side."
+                .into(),
+            parameters: vec![Parameter::new("sections", &self.0)],
+            examples,
+        }
+    }
 }
@@ -6,61 +6,69 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
-use comrak::nodes::{Ast, NodeHeading, NodeValue};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-use crate::lints::{Context, Error, Lint};
+use super::sections::{self, Match};
 
-#[derive(Debug)]
-pub struct SectionRequired<'n>(pub &'n [&'n str]);
+use serde::Deserialize;
 
-impl<'n> Lint for SectionRequired<'n> {
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct SectionRequired<S>(pub Vec<S>);
+
+impl<S> Lint for SectionRequired<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        // Collect the headings.
-        let headings: Vec<_> = ctx
-            .body()
-            .descendants()
-            // Find all headings of level 2.
-            .filter(|start| {
-                matches!(
-                    &*start.data.borrow(),
-                    Ast {
-                        value: NodeValue::Heading(NodeHeading { level: 2, .. }),
-                        ..
-                    }
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+        let headings = sections::headings(ctx.body());
+        let matches = sections::match_sections(&headings, &names);
+
+        // Headings that are probably meant to be a required section count as
+        // present, but get their own error.
+        for (heading, matched) in headings.iter().zip(&matches) {
+            let name = match matched {
+                Some(Match { exact: false, name }) => name,
+                _ => continue,
+            };
+
+            let (title, annotation) = if heading.text == *name {
+                (
+                    format!("heading for section `{}` should be plain text", name),
+                    "remove the formatting".to_owned(),
                 )
-            })
-            // Descend into their children.
-            .map(|heading| {
-                let collected: Vec<_> = heading
-                    .descendants()
-                    .skip(1)
-                    // Filter for text nodes.
-                    .filter_map(|child| match &*child.data.borrow() {
-                        Ast {
-                            value: NodeValue::Text(v),
-                            ..
-                        } => Some(v.to_vec()),
-                        _ => None,
-                    })
-                    .flatten()
-                    .collect();
-                collected
-            })
-            .filter_map(|v| String::from_utf8(v).ok())
-            .collect();
+            } else {
+                (
+                    format!("heading for section `{}` is misspelled", name),
+                    format!("did you mean `{}`?", name),
+                )
+            };
+            let help = format!("change the heading to `## {}`", name);
+
+            let data = heading.node.data.borrow();
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    annotation_type: AnnotationType::Error,
+                    id: Some(slug),
+                    label: Some(&title),
+                }),
+                slices: vec![ctx.slice_for(&data, AnnotationType::Error, &annotation)],
+                footer: vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some(&help),
+                }],
+                opt: Default::default(),
+            })?;
+        }
 
         // Use a `Vec` here to preserve the order of sections.
-        let mut missing = self.0.to_vec();
-
-        // TODO: I'm sure this is horribly inefficient!
-        missing.retain(|i| {
-            for text in &headings {
-                if *i == text {
-                    return false;
-                }
-            }
-            true
-        });
+        let missing: Vec<_> = names
+            .into_iter()
+            .filter(|n| !matches.iter().flatten().any(|m| m.name == *n))
+            .collect();
 
         if missing.is_empty() {
             return Ok(());
@@ -86,4 +94,38 @@ impl<'n> Lint for SectionRequired<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |names: &[&str]| {
+            let sections: String = names
+                .iter()
+                .map(|n| format!("\n## {}\n\nText.\n", n))
+                .collect();
+            format!("---\nid: 1\n---\n{}", sections)
+        };
+
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        let mut examples = vec![Example::pass(example(&names))];
+
+        if let Some((_, rest)) = names.split_last() {
+            examples.push(Example::fail(example(rest)));
+        }
+
+        if let Some((last, rest)) = names.split_last() {
+            let lower = last.to_lowercase();
+            if lower != *last {
+                let mut misspelled = rest.to_vec();
+                misspelled.push(&lower);
+                examples.push(Example::fail(example(&misspelled)));
+            }
+        }
+
+        Metadata {
+            summary: "Required sections are present in the body of the proposal.".into(),
+            rationale: "Reviewers expect the same information from every applicant.".into(),
+            parameters: vec![Parameter::new("sections", &self.0)],
+            examples,
+        }
+    }
 }
@@ -0,0 +1,273 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Level two headings, and matching them against the configured section names.
+
+use comrak::nodes::{Ast, AstNode, NodeCode, NodeHeading, NodeValue};
+
+#[derive(Debug)]
+pub(crate) struct Heading<'a> {
+    pub node: &'a AstNode<'a>,
+    pub line: u32,
+
+    /// The heading with any inline formatting removed.
+    pub text: String,
+
+    /// Whether the heading contains anything other than plain text.
+    pub formatted: bool,
+}
+
+/// The section a heading was matched to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Match<'n> {
+    pub name: &'n str,
+
+    /// `false` when the heading only resembles `name` (eg. differs in case,
+    /// plurals, spelling, or formatting.)
+    pub exact: bool,
+}
+
+/// Collects all the level two headings in `body`.
+pub(crate) fn headings<'a>(body: &'a AstNode<'a>) -> Vec<Heading<'a>> {
+    body.descendants()
+        .filter_map(|node| match &*node.data.borrow() {
+            Ast {
+                value: NodeValue::Heading(NodeHeading { level: 2, .. }),
+                start_line,
+                ..
+            } => Some((node, *start_line)),
+            _ => None,
+        })
+        .map(|(node, line)| {
+            let (text, formatted) = heading_text(node);
+
+            Heading {
+                node,
+                line,
+                text,
+                formatted,
+            }
+        })
+        .collect()
+}
+
+/// The text of a heading with any inline formatting removed, and whether
+/// there was any formatting.
+pub(crate) fn heading_text<'a>(node: &'a AstNode<'a>) -> (String, bool) {
+    let mut text = Vec::new();
+    let mut formatted = false;
+
+    for child in node.descendants().skip(1) {
+        match &child.data.borrow().value {
+            NodeValue::Text(v) => text.extend_from_slice(v),
+            NodeValue::Code(NodeCode { literal, .. }) => {
+                formatted = true;
+                text.extend_from_slice(literal);
+            }
+            _ => formatted = true,
+        }
+    }
+
+    (String::from_utf8_lossy(&text).into_owned(), formatted)
+}
+
+/// The blocks after `heading`, up to the next heading of the same or a higher
+/// level.
+pub(crate) fn content<'a>(heading: &'a AstNode<'a>) -> impl Iterator<Item = &'a AstNode<'a>> {
+    let level = level(heading).unwrap_or(u32::MAX);
+
+    heading
+        .following_siblings()
+        .skip(1)
+        .take_while(move |node| !matches!(self::level(node), Some(l) if l <= level))
+}
+
+/// The level of `node`, if it's a heading.
+pub(crate) fn level<'a>(node: &'a AstNode<'a>) -> Option<u32> {
+    match &node.data.borrow().value {
+        NodeValue::Heading(NodeHeading { level, .. }) => Some(*level),
+        _ => None,
+    }
+}
+
+/// Renders `nodes` as plain text, with all whitespace collapsed to single
+/// spaces. Raw HTML is left out.
+pub(crate) fn plain_text<'a, I>(nodes: I) -> String
+where
+    I: IntoIterator<Item = &'a AstNode<'a>>,
+{
+    let mut text = Vec::new();
+
+    for node in nodes.into_iter().flat_map(|n| n.descendants()) {
+        let value = &node.data.borrow().value;
+
+        if value.block() {
+            text.push(b' ');
+        }
+
+        match value {
+            NodeValue::Text(v) | NodeValue::Code(NodeCode { literal: v, .. }) => {
+                text.extend_from_slice(v)
+            }
+            NodeValue::CodeBlock(block) => text.extend_from_slice(&block.literal),
+            NodeValue::SoftBreak | NodeValue::LineBreak => text.push(b' '),
+            _ => (),
+        }
+    }
+
+    String::from_utf8_lossy(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Finds the section each heading is meant to be, if any.
+///
+/// Headings whose text is exactly a section name are matched first. The rest
+/// are then matched, in order, to the closest section name that hasn't been
+/// claimed by another heading. The returned `Vec` has one entry per heading.
+pub(crate) fn match_sections<'n>(
+    headings: &[Heading],
+    names: &[&'n str],
+) -> Vec<Option<Match<'n>>> {
+    let mut matches: Vec<_> = headings
+        .iter()
+        .map(|h| {
+            names.iter().find(|n| **n == h.text).map(|name| Match {
+                name,
+                exact: !h.formatted,
+            })
+        })
+        .collect();
+
+    let mut claimed: Vec<&str> = matches.iter().flatten().map(|m| m.name).collect();
+
+    for (heading, matched) in headings.iter().zip(matches.iter_mut()) {
+        if matched.is_some() {
+            continue;
+        }
+
+        let candidates = names.iter().filter(|n| !claimed.contains(n));
+
+        if let Some(name) = closest(&heading.text, candidates) {
+            claimed.push(name);
+            *matched = Some(Match { name, exact: false });
+        }
+    }
+
+    matches
+}
+
+fn closest<'n, 'i, I>(text: &str, names: I) -> Option<&'n str>
+where
+    I: IntoIterator<Item = &'i &'n str>,
+    'n: 'i,
+{
+    let text = normalize(text);
+
+    names
+        .into_iter()
+        .map(|name| {
+            let normal = normalize(name);
+            let threshold = (normal.chars().count() / 4).max(1);
+            (strsim::levenshtein(&text, &normal), threshold, *name)
+        })
+        .filter(|(distance, threshold, _)| distance <= threshold)
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, _, name)| name)
+}
+
+/// Lowercases `text`, collapses punctuation and whitespace, and strips plurals.
+fn normalize(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let word = word.to_lowercase();
+            match word.strip_suffix('s') {
+                Some(singular) if singular.len() > 2 => singular.to_owned(),
+                _ => word,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use comrak::{parse_document, Arena, ComrakOptions};
+
+    const NAMES: &[&str] = &[
+        "Introduction",
+        "Social Profiles",
+        "Relevant Experience",
+        "Project Affiliations",
+    ];
+
+    fn matched(markdown: &str) -> Vec<Option<(&'static str, bool)>> {
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &ComrakOptions::default());
+        let headings = headings(root);
+        match_sections(&headings, NAMES)
+            .into_iter()
+            .map(|m| m.map(|m| (m.name, m.exact)))
+            .collect()
+    }
+
+    #[test]
+    fn exact() {
+        let found = matched("## Introduction\n\n## Social Profiles\n");
+        assert_eq!(
+            found,
+            [
+                Some(("Introduction", true)),
+                Some(("Social Profiles", true))
+            ]
+        );
+    }
+
+    #[test]
+    fn near_misses() {
+        let found = matched(
+            "## **Introduction**\n\n## Social profiles\n\n## Relevent Experiance\n\n\
+             ## Projects Affiliation\n\n## Budget\n",
+        );
+        assert_eq!(
+            found,
+            [
+                Some(("Introduction", false)),
+                Some(("Social Profiles", false)),
+                Some(("Relevant Experience", false)),
+                Some(("Project Affiliations", false)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn content_and_plain_text() {
+        let arena = Arena::new();
+        let root = parse_document(
+            &arena,
+            "## One\n\nSome *emphasized*\ntext.\n\n### Sub\n\n- `code`\n\n## Two\n\nOther\n",
+            &ComrakOptions::default(),
+        );
+        let headings = headings(root);
+
+        let one = plain_text(content(headings[0].node));
+        assert_eq!(one, "Some emphasized text. Sub code");
+
+        let two = plain_text(content(headings[1].node));
+        assert_eq!(two, "Other");
+    }
+
+    #[test]
+    fn exact_match_is_preferred() {
+        let found = matched("## Social profile\n\n## Social Profiles\n");
+        assert_eq!(found, [None, Some(("Social Profiles", true))]);
+    }
+}
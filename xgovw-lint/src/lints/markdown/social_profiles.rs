@@ -0,0 +1,577 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Profiles listed in the `Social Profiles` section, and a lint to check
+//! them.
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
+
+use comrak::nodes::{AstNode, NodeValue};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::preamble::author;
+
+use regex::Regex;
+
+use serde::Deserialize;
+
+use std::fmt::{self, Debug, Display};
+use std::ops::Range;
+
+use url::Url;
+
+use super::sections;
+
+/// Words that name a platform in text, like `GitHub: @username`.
+const KEYWORD: &str = r"(?i:\b(github|twitter|linkedin|discord|telegram)\b)|\b(X)\b";
+
+/// A handle after a platform name, like `GitHub: username`.
+const LABELLED: &str = r"^\s*[:\-–]\s*@?([^\s,;()\[\]]+)";
+
+/// A handle on its own, like `@username` or `name#1234`.
+const HANDLE: &str = r"(?:^|[\s(])@([A-Za-z0-9_.\-]+)|\b([A-Za-z0-9_.]+#\d{4})\b";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Platform {
+    GitHub,
+    X,
+    LinkedIn,
+    Discord,
+    Telegram,
+    Website,
+}
+
+impl Platform {
+    fn from_keyword(keyword: &str) -> Self {
+        match keyword.to_lowercase().as_str() {
+            "github" => Self::GitHub,
+            "linkedin" => Self::LinkedIn,
+            "discord" => Self::Discord,
+            "telegram" => Self::Telegram,
+            _ => Self::X,
+        }
+    }
+
+    /// Whether `handle` could be an account on this platform.
+    pub fn is_valid(&self, handle: &str) -> bool {
+        let all = |f: fn(char) -> bool| handle.chars().all(f);
+        let len = handle.chars().count();
+
+        match self {
+            Self::GitHub => author::is_github_username(handle),
+            Self::X => (1..=15).contains(&len) && all(|c| c.is_ascii_alphanumeric() || c == '_'),
+            Self::LinkedIn => {
+                (3..=100).contains(&len)
+                    && all(|c| c.is_alphanumeric() || c == '-' || c == '_' || c == '%')
+            }
+            Self::Discord => match handle.split_once('#') {
+                Some((name, tag)) => {
+                    (2..=32).contains(&name.chars().count())
+                        && tag.len() == 4
+                        && tag.chars().all(|c| c.is_ascii_digit())
+                }
+                None => {
+                    (2..=32).contains(&len)
+                        && !handle.contains("..")
+                        && all(|c| {
+                            c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.'
+                        })
+                }
+            },
+            Self::Telegram => {
+                (5..=32).contains(&len)
+                    && handle.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && all(|c| c.is_ascii_alphanumeric() || c == '_')
+            }
+            Self::Website => true,
+        }
+    }
+
+    fn rules(&self) -> &'static str {
+        match self {
+            Self::GitHub => {
+                "GitHub usernames have up to 39 letters, digits, or single hyphens, and can't \
+                start or end with a hyphen"
+            }
+            Self::X => "X usernames have up to 15 letters, digits, or underscores",
+            Self::LinkedIn => "LinkedIn profile names have 3 to 100 letters, digits, or hyphens",
+            Self::Discord => {
+                "Discord usernames have 2 to 32 lowercase letters, digits, underscores, or \
+                periods"
+            }
+            Self::Telegram => {
+                "Telegram usernames have 5 to 32 letters, digits, or underscores, starting \
+                with a letter"
+            }
+            Self::Website => "",
+        }
+    }
+}
+
+impl Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::GitHub => "GitHub",
+            Self::X => "X",
+            Self::LinkedIn => "LinkedIn",
+            Self::Discord => "Discord",
+            Self::Telegram => "Telegram",
+            Self::Website => "website",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// One entry in the `Social Profiles` section.
+#[derive(Debug, Clone)]
+pub struct Profile<'a> {
+    pub platform: Platform,
+
+    /// The account name, if there is one. `None` for websites, and for links
+    /// that don't point at a particular account.
+    pub handle: Option<String>,
+
+    /// Where the profile links to, if it's a link.
+    pub url: Option<String>,
+
+    /// The link or text node the profile was found in.
+    pub node: &'a AstNode<'a>,
+
+    /// Where the handle is in the text of `node`, for profiles found in text.
+    pub range: Option<Range<usize>>,
+}
+
+impl<'a> Profile<'a> {
+    /// Whether following the profile's link shows who owns it.
+    pub fn is_verifiable(&self) -> bool {
+        self.url.is_some() && (self.handle.is_some() || self.platform == Platform::Website)
+    }
+}
+
+/// Finds the profiles in `nodes`, usually the contents of the `Social Profiles`
+/// section.
+pub fn extract<'a, I>(nodes: I) -> Vec<Profile<'a>>
+where
+    I: IntoIterator<Item = &'a AstNode<'a>>,
+{
+    let keyword = Regex::new(KEYWORD).unwrap();
+    let labelled = Regex::new(LABELLED).unwrap();
+    let handle = Regex::new(HANDLE).unwrap();
+
+    let mut profiles = vec![];
+
+    for node in nodes.into_iter().flat_map(|n| n.descendants()) {
+        let text = match &node.data.borrow().value {
+            NodeValue::Link(link) => {
+                let url = String::from_utf8_lossy(&link.url).into_owned();
+                if let Some((platform, handle)) = classify(&url) {
+                    profiles.push(Profile {
+                        platform,
+                        handle,
+                        url: Some(url),
+                        node,
+                        range: None,
+                    });
+                }
+                continue;
+            }
+            NodeValue::Text(text) => String::from_utf8_lossy(text).into_owned(),
+            _ => continue,
+        };
+
+        // Text inside a link describes the link, not another profile.
+        if node.ancestors().any(|a| {
+            matches!(
+                a.data.borrow().value,
+                NodeValue::Link(_) | NodeValue::Image(_)
+            )
+        }) {
+            continue;
+        }
+
+        let context = line_context(node);
+        let mut found = vec![];
+
+        for captures in keyword.captures_iter(&text) {
+            let name = captures.get(1).or_else(|| captures.get(2)).unwrap();
+            let platform = Platform::from_keyword(name.as_str());
+
+            if let Some(m) = labelled.captures(&text[name.end()..]) {
+                let m = m.get(1).unwrap();
+                let range = name.end() + m.start()..name.end() + m.end();
+                found.push((platform, range));
+            }
+        }
+
+        let platform = keyword
+            .captures(&text)
+            .or_else(|| keyword.captures(&context))
+            .map(|c| Platform::from_keyword(c.get(1).or_else(|| c.get(2)).unwrap().as_str()));
+
+        for captures in handle.captures_iter(&text) {
+            let (m, platform) = match (captures.get(1), captures.get(2), platform) {
+                (Some(m), _, Some(p)) => (m, p),
+                (_, Some(m), _) => (m, Platform::Discord),
+                _ => continue,
+            };
+
+            if found.iter().any(|(_, r)| r.contains(&m.start())) {
+                continue;
+            }
+
+            found.push((platform, m.range()));
+        }
+
+        found.sort_by_key(|(_, r)| r.start);
+
+        for (platform, range) in found {
+            let handle = text[range.clone()].trim_end_matches('.').to_owned();
+            let range = range.start..range.start + handle.len();
+
+            profiles.push(Profile {
+                platform,
+                handle: Some(handle),
+                url: None,
+                node,
+                range: Some(range),
+            });
+        }
+    }
+
+    profiles
+}
+
+/// The text before `node` on the same line, like `**GitHub**: ` before
+/// `@username`.
+fn line_context<'a>(node: &'a AstNode<'a>) -> String {
+    let mut context = vec![];
+
+    for sibling in node.preceding_siblings().skip(1) {
+        match &sibling.data.borrow().value {
+            NodeValue::SoftBreak | NodeValue::LineBreak => break,
+            NodeValue::Text(t) => context.push(String::from_utf8_lossy(t).into_owned()),
+            _ => {
+                for child in sibling.descendants() {
+                    if let NodeValue::Text(t) = &child.data.borrow().value {
+                        context.push(String::from_utf8_lossy(t).into_owned());
+                    }
+                }
+            }
+        }
+    }
+
+    context.reverse();
+    context.join(" ")
+}
+
+/// The platform a link points to, and the account name in it.
+fn classify(url: &str) -> Option<(Platform, Option<String>)> {
+    let url = Url::parse(url).ok()?;
+
+    if url.scheme() != "https" && url.scheme() != "http" {
+        return None;
+    }
+
+    let host = url.host_str()?.to_lowercase();
+    let host = host.strip_prefix("www.").unwrap_or(&host);
+
+    let segments: Vec<_> = url
+        .path_segments()
+        .map(|s| s.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    let first = |skip: &[&str]| {
+        segments
+            .first()
+            .filter(|s| !skip.contains(s))
+            .map(|s| s.to_string())
+    };
+
+    let found = match host {
+        "github.com" => match segments.as_slice() {
+            ["orgs", org, ..] => (Platform::GitHub, Some(org.to_string())),
+            _ => (Platform::GitHub, first(&["sponsors", "settings", "topics"])),
+        },
+        "twitter.com" | "mobile.twitter.com" | "x.com" => (
+            Platform::X,
+            first(&["i", "home", "intent", "search", "hashtag"])
+                .map(|h| h.trim_start_matches('@').to_owned()),
+        ),
+        "linkedin.com" => match segments.as_slice() {
+            ["in" | "company", name, ..] => (Platform::LinkedIn, Some(name.to_string())),
+            _ => (Platform::LinkedIn, None),
+        },
+        "discord.com" | "discord.gg" | "discordapp.com" => (Platform::Discord, None),
+        "t.me" | "telegram.me" => (Platform::Telegram, first(&["joinchat", "s"])),
+        _ => (Platform::Website, None),
+    };
+
+    Some(found)
+}
+
+/// The `section` of the body lists valid profiles, including at least one
+/// link, and includes the GitHub users from the `author` header.
+#[derive(Debug, Deserialize)]
+pub struct SocialProfiles<S> {
+    pub section: S,
+    pub author: S,
+}
+
+impl<S> SocialProfiles<S>
+where
+    S: Display + AsRef<str>,
+{
+    fn report(
+        &self,
+        ctx: &Context,
+        slug: &str,
+        title: &str,
+        slice: Slice,
+        footer: Option<(AnnotationType, &str)>,
+    ) -> Result<(), Error> {
+        ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(slug),
+                label: Some(title),
+            }),
+            slices: vec![slice],
+            footer: footer
+                .into_iter()
+                .map(|(annotation_type, label)| Annotation {
+                    annotation_type,
+                    id: None,
+                    label: Some(label),
+                })
+                .collect(),
+            opt: Default::default(),
+        })
+    }
+}
+
+impl<S> Lint for SocialProfiles<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let headings = sections::headings(ctx.body());
+        let matches = sections::match_sections(&headings, &[self.section.as_ref()]);
+
+        let heading = match headings.iter().zip(matches).find(|(_, m)| m.is_some()) {
+            Some((h, _)) => h,
+            None => return Ok(()),
+        };
+
+        let profiles = extract(sections::content(heading.node));
+
+        for profile in &profiles {
+            let data = profile.node.data.borrow();
+
+            let handle = match &profile.handle {
+                Some(h) => h,
+                None if profile.platform == Platform::Website
+                    || profile.platform == Platform::Discord =>
+                {
+                    continue
+                }
+                None => {
+                    let title = format!("link doesn't point to a {} profile", profile.platform);
+                    let slice = ctx.slice_for(&data, AnnotationType::Error, "not a profile");
+                    self.report(ctx, slug, &title, slice, None)?;
+                    continue;
+                }
+            };
+
+            if profile.platform.is_valid(handle) {
+                continue;
+            }
+
+            let title = format!("`{}` is not a valid {} username", handle, profile.platform);
+
+            let span = match (&data.value, &profile.range) {
+                (NodeValue::Text(text), Some(range)) => ctx.text_span(&data, text, range.clone()),
+                _ => ctx.span(&data),
+            };
+
+            let slice = match span {
+                Some(s) => ctx.span_slice(s, AnnotationType::Error, "invalid username"),
+                None => ctx.slice_for(&data, AnnotationType::Error, "invalid username"),
+            };
+
+            let rules = profile.platform.rules();
+            self.report(
+                ctx,
+                slug,
+                &title,
+                slice,
+                Some((AnnotationType::Note, rules)),
+            )?;
+        }
+
+        let data = heading.node.data.borrow();
+
+        if !profiles.iter().any(Profile::is_verifiable) {
+            let title = format!(
+                "section `{}` must link to at least one profile",
+                self.section
+            );
+            let slice = ctx.slice_for(&data, AnnotationType::Error, "no links to profiles");
+            self.report(
+                ctx,
+                slug,
+                &title,
+                slice,
+                Some((
+                    AnnotationType::Help,
+                    "link to a profile, like `[@username](https://github.com/username)`",
+                )),
+            )?;
+        }
+
+        let field = match ctx.preamble().by_name(self.author.as_ref()) {
+            Some(f) => f,
+            None => return Ok(()),
+        };
+
+        let authors = field.authors();
+        let usernames = authors
+            .iter()
+            .filter_map(|a| a.value().as_ref().ok()?.github);
+
+        for username in usernames {
+            let listed = profiles.iter().any(|p| {
+                p.platform == Platform::GitHub
+                    && p.handle
+                        .as_deref()
+                        .map(|h| h.eq_ignore_ascii_case(username))
+                        == Some(true)
+            });
+
+            if listed {
+                continue;
+            }
+
+            let title = format!(
+                "GitHub user `@{}` from `{}` is not in section `{}`",
+                username, self.author, self.section
+            );
+            let help = format!(
+                "add `[@{0}](https://github.com/{0})` to the section",
+                username
+            );
+
+            let slice = Slice {
+                line_start: field.line_start(),
+                fold: false,
+                origin: ctx.origin(),
+                source: field.source(),
+                annotations: vec![],
+            };
+
+            self.report(
+                ctx,
+                slug,
+                &title,
+                slice,
+                Some((AnnotationType::Help, &help)),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "The `{}` section lists valid profiles, including the authors' GitHub accounts.",
+                self.section
+            ),
+            rationale: "Voters check who is behind an application through their public \
+                profiles, which only works if the profiles are real, linked, and belong to \
+                the authors."
+                .into(),
+            parameters: vec![
+                Parameter::new("section", &self.section),
+                Parameter::new("author", &self.author),
+            ],
+            examples: vec![
+                Example::pass(format!(
+                    "---\n{}: Random J. User (@username)\n---\n\n## {}\n\n\
+                    - GitHub: [@username](https://github.com/username)\n\
+                    - Discord: username\n",
+                    self.author, self.section
+                )),
+                Example::fail(format!(
+                    "---\n{}: Random J. User (@username)\n---\n\n## {}\n\n@username GitHub\n",
+                    self.author, self.section
+                )),
+                Example::fail(format!(
+                    "---\n{}: Random J. User (@username)\n---\n\n## {}\n\n\
+                    - [GitHub](https://github.com/someone-else)\n\
+                    - X: @this_is_far_too_long\n",
+                    self.author, self.section
+                )),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use comrak::{parse_document, Arena, ComrakOptions};
+
+    fn profiles(markdown: &str) -> Vec<(Platform, Option<String>, bool)> {
+        let arena = Arena::new();
+        let root = parse_document(&arena, markdown, &ComrakOptions::default());
+
+        extract(root.children())
+            .into_iter()
+            .map(|p| (p.platform, p.handle, p.url.is_some()))
+            .collect()
+    }
+
+    #[test]
+    fn text_and_links() {
+        let found = profiles(
+            "@SudoWeezy Github\n\n\
+            - **Twitter**: @algo_fan\n\
+            - Discord: weezy.dev, or old#1234\n\
+            - [LinkedIn](https://www.linkedin.com/in/some-one/)\n\
+            - [@sudoweezy](https://github.com/sudoweezy)\n\
+            - <https://t.me/weezy_tg>\n\
+            - [My site](https://example.com)\n",
+        );
+
+        let handle = |h: &str| Some(h.to_owned());
+
+        assert_eq!(
+            found,
+            [
+                (Platform::GitHub, handle("SudoWeezy"), false),
+                (Platform::X, handle("algo_fan"), false),
+                (Platform::Discord, handle("weezy.dev"), false),
+                (Platform::Discord, handle("old#1234"), false),
+                (Platform::LinkedIn, handle("some-one"), true),
+                (Platform::GitHub, handle("sudoweezy"), true),
+                (Platform::Telegram, handle("weezy_tg"), true),
+                (Platform::Website, None, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles() {
+        assert!(Platform::X.is_valid("algo_fan"));
+        assert!(!Platform::X.is_valid("this_is_far_too_long"));
+        assert!(Platform::Discord.is_valid("weezy.dev"));
+        assert!(!Platform::Discord.is_valid("Weezy"));
+        assert!(!Platform::Telegram.is_valid("1abcde"));
+        assert!(!Platform::GitHub.is_valid("-bad"));
+    }
+}
@@ -0,0 +1,388 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
+
+use comrak::nodes::{Ast, NodeCode, NodeValue, TableAlignment};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::span::{self, Span};
+use crate::tree::{self, Next, TraverseExt};
+
+use serde::Deserialize;
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+
+use super::sections;
+
+/// The columns a table in a section must have.
+#[derive(Debug, Deserialize)]
+pub struct Columns<S> {
+    pub section: S,
+    pub names: Vec<S>,
+}
+
+/// Tables have a header row with no empty cells, and every row has as many
+/// cells as the header.
+///
+/// Tables in the sections listed in `columns` must have exactly the given
+/// column names, in order.
+#[derive(Debug, Deserialize)]
+pub struct Tables<S> {
+    #[serde(default)]
+    pub columns: Vec<Columns<S>>,
+}
+
+impl<S> Lint for Tables<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let names: Vec<_> = self.columns.iter().map(|c| c.section.as_ref()).collect();
+        let headings = sections::headings(ctx.body());
+        let matches = sections::match_sections(&headings, &names);
+
+        // The required columns for each table, by the line it starts on.
+        let mut required = HashMap::new();
+
+        for (heading, matched) in headings.iter().zip(matches) {
+            let matched = match matched {
+                Some(m) => m,
+                None => continue,
+            };
+
+            let columns = self
+                .columns
+                .iter()
+                .find(|c| c.section.as_ref() == matched.name)
+                .unwrap();
+
+            for node in sections::content(heading.node).flat_map(|n| n.descendants()) {
+                let data = node.data.borrow();
+                if let NodeValue::Table(_) = data.value {
+                    required.insert(data.start_line, columns);
+                }
+            }
+        }
+
+        let mut visitor = Visitor {
+            ctx,
+            slug,
+            required,
+            columns: None,
+            header: None,
+        };
+
+        ctx.body().traverse().visit(&mut visitor)?;
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let (section, names) = match self.columns.first() {
+            Some(c) => (
+                c.section.to_string(),
+                c.names.iter().map(ToString::to_string).collect(),
+            ),
+            None => (
+                "Project Affiliations".into(),
+                vec!["Project".into(), "Role".into()],
+            ),
+        };
+
+        let row = |cells: &[String]| format!("| {} |", cells.join(" | "));
+        let delimiter = vec!["---".to_owned(); names.len()];
+        let values: Vec<_> = (1..=names.len()).map(|i| i.to_string()).collect();
+
+        let mut more = values.clone();
+        more.push("extra".into());
+
+        Metadata {
+            summary: "Tables are well formed.".into(),
+            rationale: "Rows with missing or extra cells are silently padded or cut off when \
+                rendered, and tables without headers aren't rendered as tables at all."
+                .into(),
+            parameters: vec![Parameter::new("columns", &self.columns)],
+            examples: vec![
+                Example::pass(format!(
+                    "---\nid: 1\n---\n\n## {}\n\n{}\n{}\n{}\n",
+                    section,
+                    row(&names),
+                    row(&delimiter),
+                    row(&values)
+                )),
+                Example::fail(format!(
+                    "---\nid: 1\n---\n\n## {}\n\n{}\n{}\n{}\n",
+                    section,
+                    row(&names),
+                    row(&delimiter),
+                    row(&more)
+                )),
+                Example::fail(format!(
+                    "---\nid: 1\n---\n\n## {}\n\n{}\n{}\n",
+                    section,
+                    row(&names),
+                    row(&values)
+                )),
+            ],
+        }
+    }
+}
+
+/// The header row of the table being visited.
+struct Header {
+    span: Option<Span>,
+    line: u32,
+    cells: Vec<(Option<Span>, String)>,
+}
+
+struct Visitor<'a, 'b, 'c, S> {
+    ctx: &'c Context<'a, 'b>,
+    slug: &'c str,
+    required: HashMap<u32, &'c Columns<S>>,
+
+    /// The number of columns in the table being visited.
+    columns: Option<usize>,
+
+    /// Set while inside a header row.
+    header: Option<Header>,
+}
+
+impl<'a, 'b, 'c, S> Visitor<'a, 'b, 'c, S>
+where
+    S: Display + AsRef<str>,
+{
+    fn report(
+        &self,
+        title: &str,
+        slice: Slice,
+        footer: Option<(AnnotationType, &str)>,
+    ) -> Result<(), Error> {
+        self.ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(self.slug),
+                label: Some(title),
+            }),
+            slices: vec![slice],
+            footer: footer
+                .into_iter()
+                .map(|(annotation_type, label)| Annotation {
+                    annotation_type,
+                    id: None,
+                    label: Some(label),
+                })
+                .collect(),
+            opt: Default::default(),
+        })
+    }
+
+    fn slice<'s>(&self, ast: &Ast, span: Option<Span>, label: &'s str) -> Slice<'s>
+    where
+        'a: 's,
+    {
+        match span {
+            Some(span) => self.ctx.span_slice(span, AnnotationType::Error, label),
+            None => self.ctx.slice_for(ast, AnnotationType::Error, label),
+        }
+    }
+
+    fn check_header(&self, ast: &Ast, header: &Header) -> Result<(), Error> {
+        for (span, text) in &header.cells {
+            // Cells with only an image have no text, but aren't empty.
+            let empty = match span {
+                Some(s) => self.ctx.source()[s.bytes()].trim().is_empty(),
+                None => text.trim().is_empty(),
+            };
+
+            if !empty {
+                continue;
+            }
+
+            let slice = self.slice(ast, *span, "empty header cell");
+            self.report(
+                "table header cells must not be empty",
+                slice,
+                Some((AnnotationType::Help, "give every column a name")),
+            )?;
+        }
+
+        let columns = match self.required.get(&header.line) {
+            Some(c) => c,
+            None => return Ok(()),
+        };
+
+        let found: Vec<_> = header.cells.iter().map(|(_, t)| t.trim()).collect();
+
+        let matches = found.len() == columns.names.len()
+            && found
+                .iter()
+                .zip(&columns.names)
+                .all(|(f, n)| f.eq_ignore_ascii_case(n.as_ref()));
+
+        if matches {
+            return Ok(());
+        }
+
+        let names: Vec<_> = columns.names.iter().map(AsRef::as_ref).collect();
+        let title = format!(
+            "tables in `{}` must have the columns `{}`",
+            columns.section,
+            names.join(" | ")
+        );
+        let label = format!("found `{}`", found.join(" | "));
+
+        let slice = self.slice(ast, header.span, &label);
+        self.report(&title, slice, None)
+    }
+}
+
+impl<'a, 'b, 'c, S> tree::Visitor for Visitor<'a, 'b, 'c, S>
+where
+    S: Display + AsRef<str>,
+{
+    type Error = Error;
+
+    fn enter_paragraph(&mut self, ast: &Ast) -> Result<Next, Self::Error> {
+        let span = match self.ctx.span(ast) {
+            Some(s) => s,
+            None => return Ok(Next::TraverseChildren),
+        };
+
+        let source = self.ctx.span_source(span).source;
+        let lines: Vec<_> = source.lines().map(str::trim).collect();
+
+        // Only paragraphs that look like a table with something wrong in its
+        // first two rows.
+        if lines.len() < 2 || !lines.iter().all(|l| l.starts_with('|')) {
+            return Ok(Next::TraverseChildren);
+        }
+
+        let is_delimiter = lines[1].contains('-')
+            && lines[1]
+                .chars()
+                .all(|c| matches!(c, '|' | '-' | ':' | ' ' | '\t'));
+
+        let (title, help) = if is_delimiter {
+            let title = format!(
+                "table header has {} column(s), but its delimiter row has {}",
+                span::cells(lines[0]).len(),
+                span::cells(lines[1]).len()
+            );
+            (title, "give the delimiter row one `---` for each column")
+        } else {
+            (
+                "table has no header row".to_owned(),
+                "add a delimiter row (like `| --- | --- |`) after the first row",
+            )
+        };
+
+        let slice = self
+            .ctx
+            .span_slice(span, AnnotationType::Error, "not rendered as a table");
+        self.report(&title, slice, Some((AnnotationType::Help, help)))?;
+
+        Ok(Next::SkipChildren)
+    }
+
+    fn enter_table(&mut self, _: &Ast, align: &[TableAlignment]) -> Result<Next, Self::Error> {
+        self.columns = Some(align.len());
+        Ok(Next::TraverseChildren)
+    }
+
+    fn depart_table(&mut self, _: &Ast, _: &[TableAlignment]) -> Result<(), Self::Error> {
+        self.columns = None;
+        self.header = None;
+        Ok(())
+    }
+
+    fn enter_table_row(&mut self, ast: &Ast, header: bool) -> Result<Next, Self::Error> {
+        let span = self.ctx.span(ast);
+
+        if header {
+            self.header = Some(Header {
+                span,
+                line: ast.start_line,
+                cells: vec![],
+            });
+            return Ok(Next::TraverseChildren);
+        }
+
+        let expected = match self.columns {
+            Some(c) => c,
+            None => return Ok(Next::TraverseChildren),
+        };
+
+        // The row's span leaves out block quote markers, which the raw line
+        // doesn't.
+        let row = match span {
+            Some(s) => &self.ctx.source()[s.bytes()],
+            None => self.ctx.line(ast.start_line),
+        };
+
+        let found = span::cells(row).len();
+
+        if found == expected {
+            return Ok(Next::TraverseChildren);
+        }
+
+        let title = format!(
+            "table row has {} cell(s), but the header has {}",
+            found, expected
+        );
+
+        let help = if found > expected {
+            "extra cells are left out when the table is rendered"
+        } else {
+            "missing cells are shown empty when the table is rendered"
+        };
+
+        let label = format!("{} cell(s)", found);
+        let slice = self.slice(ast, span, &label);
+        self.report(&title, slice, Some((AnnotationType::Note, help)))?;
+
+        Ok(Next::TraverseChildren)
+    }
+
+    fn depart_table_row(&mut self, ast: &Ast, header: bool) -> Result<(), Self::Error> {
+        if !header {
+            return Ok(());
+        }
+
+        if let Some(header) = self.header.take() {
+            self.check_header(ast, &header)?;
+        }
+
+        Ok(())
+    }
+
+    fn enter_table_cell(&mut self, ast: &Ast) -> Result<Next, Self::Error> {
+        let span = self.ctx.span(ast);
+
+        if let Some(header) = &mut self.header {
+            header.cells.push((span, String::new()));
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+
+    fn enter_text(&mut self, _: &Ast, txt: &[u8]) -> Result<Next, Self::Error> {
+        if let Some((_, text)) = self.header.as_mut().and_then(|h| h.cells.last_mut()) {
+            text.push_str(&String::from_utf8_lossy(txt));
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+
+    fn enter_code(&mut self, _: &Ast, code: &NodeCode) -> Result<Next, Self::Error> {
+        if let Some((_, text)) = self.header.as_mut().and_then(|h| h.cells.last_mut()) {
+            text.push_str(&String::from_utf8_lossy(&code.literal));
+        }
+
+        Ok(Next::TraverseChildren)
+    }
+}
@@ -0,0 +1,54 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
+
+use super::lines::{self, Block};
+
+/// The body is indented with spaces, not tabs. Code blocks may use tabs.
+#[derive(Debug, Deserialize)]
+pub struct Tabs;
+
+impl Lint for Tabs {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let found: Vec<_> = lines::lines(ctx)
+            .into_iter()
+            .filter(|line| line.block != Block::Code)
+            .filter_map(|line| {
+                // Trailing tabs are reported as trailing whitespace instead.
+                let text = line.text.trim_end_matches([' ', '\t']);
+                let start = text.chars().position(|c| c == '\t')?;
+                let end = text.chars().count() - text.chars().rev().position(|c| c == '\t')?;
+                Some((line, (start, end)))
+            })
+            .collect();
+
+        lines::report(
+            ctx,
+            slug,
+            "the body must not contain tabs",
+            &found,
+            "tab",
+            "use spaces instead",
+        )
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "The body uses spaces instead of tabs (outside of code blocks).".into(),
+            rationale: "Tabs are rendered at different widths, and change how markdown lists \
+                and code are nested."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\n- one\n    - two\n"),
+                Example::fail("---\nid: 1\n---\n\n- one\n\t- two\n"),
+            ],
+        }
+    }
+}
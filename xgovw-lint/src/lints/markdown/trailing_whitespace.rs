@@ -0,0 +1,55 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
+
+use super::lines;
+
+/// Lines in the body don't end with spaces or tabs.
+#[derive(Debug, Deserialize)]
+pub struct TrailingWhitespace;
+
+impl Lint for TrailingWhitespace {
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let found: Vec<_> = lines::lines(ctx)
+            .into_iter()
+            .filter_map(|line| {
+                let trimmed = line.text.trim_end_matches([' ', '\t']);
+                if trimmed.len() == line.text.len() {
+                    return None;
+                }
+
+                let start = trimmed.chars().count();
+                Some((line, (start, line.text.chars().count())))
+            })
+            .collect();
+
+        lines::report(
+            ctx,
+            slug,
+            "lines must not end with whitespace",
+            &found,
+            "trailing whitespace",
+            "remove it (for a line break, end the line with a backslash instead)",
+        )
+    }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "Lines in the body don't end with whitespace.".into(),
+            rationale: "Trailing whitespace is invisible in most editors, but shows up in diffs \
+                and turns into line breaks when rendered."
+                .into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n\nHello\\\nworld.\n"),
+                Example::fail("---\nid: 1\n---\n\nHello  \nworld.\n"),
+            ],
+        }
+    }
+}
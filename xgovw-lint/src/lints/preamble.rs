@@ -3,12 +3,14 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
-pub mod email;
+pub mod allowed;
 pub mod author;
 pub mod date;
+pub mod email;
 pub mod file_name;
 pub mod length;
 pub mod list;
+mod names;
 pub mod no_duplicates;
 pub mod one_of;
 pub mod order;
@@ -21,9 +23,10 @@ pub mod trim;
 pub mod uint;
 pub mod url;
 
+pub use self::allowed::Allowed;
 pub use self::author::Author;
-pub use self::email::Email;
 pub use self::date::Date;
+pub use self::email::Email;
 pub use self::file_name::FileName;
 pub use self::length::Length;
 pub use self::list::List;
@@ -0,0 +1,114 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+use super::names;
+
+/// Preamble headers must be one of the given names.
+///
+/// A header that looks like a misspelling of a name missing from the preamble
+/// gets a rename suggestion, and [`Required`](super::Required) leaves the
+/// missing name for this lint to report.
+#[derive(Debug, Deserialize)]
+pub struct Allowed<S>(pub Vec<S>);
+
+impl<S> Lint for Allowed<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
+    fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        for (field, suggestion) in names::misspellings(ctx.preamble(), &names) {
+            let label = format!("preamble header `{}` is not allowed", field.name());
+
+            let annotation;
+            let rename;
+            let missing;
+            let mut footer = vec![];
+
+            let annotation_label = match suggestion {
+                Some(name) => {
+                    annotation = format!("did you mean `{}`?", name);
+                    missing = format!("`{}` is missing from the preamble", name);
+                    rename = format!("rename `{}` to `{}`", field.name(), name);
+
+                    footer.push(Annotation {
+                        annotation_type: AnnotationType::Note,
+                        id: None,
+                        label: Some(&missing),
+                    });
+                    footer.push(Annotation {
+                        annotation_type: AnnotationType::Help,
+                        id: None,
+                        label: Some(&rename),
+                    });
+
+                    annotation.as_str()
+                }
+                None => "unrecognized header",
+            };
+
+            ctx.report(Snippet {
+                title: Some(Annotation {
+                    id: Some(slug),
+                    annotation_type: AnnotationType::Error,
+                    label: Some(&label),
+                }),
+                footer,
+                slices: vec![Slice {
+                    line_start: field.line_start(),
+                    fold: false,
+                    origin: ctx.origin(),
+                    source: field.source(),
+                    annotations: vec![SourceAnnotation {
+                        annotation_type: AnnotationType::Error,
+                        label: annotation_label,
+                        range: (0, field.name().chars().count()),
+                    }],
+                }],
+                opt: Default::default(),
+            })?;
+        }
+
+        Ok(())
+    }
+
+    fn metadata(&self) -> Metadata {
+        let example = |names: &[&str]| {
+            let fields: String = names.iter().map(|n| format!("{}: value\n", n)).collect();
+            format!("---\n{}---\n", fields)
+        };
+
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        let mut examples = vec![];
+
+        if !names.is_empty() {
+            examples.push(Example::pass(example(&names)));
+        }
+
+        let mut extra = names.clone();
+        extra.push("xgovw-unknown");
+        examples.push(Example::fail(example(&extra)));
+
+        Metadata {
+            summary: "There are no preamble headers besides the allowed ones.".into(),
+            rationale: "A misspelled header is as good as a missing one, and tools reading \
+                the preamble ignore headers they don't know."
+                .into(),
+            parameters: vec![Parameter::new("names", &self.0)],
+            examples,
+        }
+    }
+}
@@ -5,63 +5,69 @@
  */
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
-use regex::RegexSet;
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::preamble::author::USERNAME;
 
-fn footer() -> Vec<Annotation<'static>> {
-    vec![
-        Annotation {
-            annotation_type: AnnotationType::Help,
-            id: None,
-            label: Some("Try `Random J. User (@username)`."),
-        }
-    ]
-}
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+use super::list;
 
-#[derive(Debug)]
-pub struct Author<'n>(pub &'n str);
+#[derive(Debug, Deserialize)]
+pub struct Author<S>(pub S);
 
-impl<'n> Lint for Author<'n> {
+impl<S> Lint for Author<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.0.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
-        // TODO: Email addresses are insane, and can probably contain commas,
-        //       parentheses, and greater-/less- than symbols. For correctness,
-        //       we should switch to a parser that can handle those cases.
-
-        let items = field.value().split(',');
-
-        let set = RegexSet::new(&[
-            r"^[^()<>,@]+ \(@[a-zA-Z\d-]+\)$", // Match a GitHub username.
-            r"^[^()<>,@]+ <[^@][^>]*@[^>]+\.[^>]+>$", // Match an email address.
-            r"^[^()<>,@]+$",                   // Match just a name.
-        ])
-        .unwrap();
+        list::separators(slug, ctx, field, &field.author_list())?;
 
         let mut has_username = false;
-        let mut offset = 0;
+        let mut has_invalid = false;
 
-        for item in items {
-            let current = offset;
-            offset += item.len() + 1;
-            let trimmed = item.trim();
+        for author in field.authors() {
+            let error = match author.value() {
+                Ok(a) => {
+                    has_username |= a.github.is_some();
+                    continue;
+                }
+                Err(e) => e,
+            };
 
-            let matches = set.matches(trimmed);
+            has_invalid = true;
 
-            if matches.matched_any() {
-                has_username |= matches.matched(0);
-                continue;
-            }
+            let suggestion = error.suggestion.as_ref().map(|s| format!("Try `{}`.", s));
+
+            let footer = match (error.title, &suggestion) {
+                (USERNAME, _) => vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some(
+                        "GitHub usernames have up to 39 letters, digits, or single hyphens, and \
+                        can't start or end with a hyphen",
+                    ),
+                }],
+                (_, Some(suggestion)) => vec![Annotation {
+                    annotation_type: AnnotationType::Help,
+                    id: None,
+                    label: Some(suggestion),
+                }],
+                (_, None) => vec![],
+            };
 
             ctx.report(Snippet {
                 title: Some(Annotation {
                     annotation_type: AnnotationType::Error,
                     id: Some(slug),
-                    label: Some("authors in the preamble must match the expected format"),
+                    label: Some(error.title),
                 }),
                 slices: vec![Slice {
                     fold: false,
@@ -70,19 +76,18 @@ impl<'n> Lint for Author<'n> {
                     source: field.source(),
                     annotations: vec![SourceAnnotation {
                         annotation_type: AnnotationType::Error,
-                        label: "unrecognized author",
-                        range: (
-                            field.name().len() + current + 1,
-                            field.name().len() + current + 1 + item.len(),
-                        ),
+                        label: error.label,
+                        range: author.sub_range(error.range.clone()),
                     }],
                 }],
-                footer: footer(),
+                footer,
                 opt: Default::default(),
             })?;
         }
 
-        if !has_username {
+        // An author that couldn't be parsed may well have been meant to have a
+        // username, so it's already been reported.
+        if !has_username && !has_invalid {
             let label = format!(
                 "preamble header `{}` must contain at least one GitHub username",
                 self.0
@@ -107,4 +112,25 @@ impl<'n> Lint for Author<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "The `{}` header is correctly formatted, and there is at least one GitHub user listed.",
+                self.0
+            ),
+            rationale: "Authors are contacted through GitHub, so at least one of them must be reachable there.".into(),
+            parameters: vec![Parameter::new("name", &self.0)],
+            examples: vec![
+                Example::pass(format!("---\n{}: Random J. User (@username)\n---\n", self.0)),
+                Example::pass(format!(
+                    "---\n{}: Random J. User (@username) <user@example.com>, Jane Doe\n---\n",
+                    self.0
+                )),
+                Example::fail(format!("---\n{}: Random J. User(@username)\n---\n", self.0)),
+                Example::fail(format!("---\n{}: A (@a),B (@b)\n---\n", self.0)),
+                Example::fail(format!("---\n{}: Random J. User (@user--name)\n---\n", self.0)),
+            ],
+        }
+    }
 }
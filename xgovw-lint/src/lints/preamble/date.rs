@@ -6,36 +6,30 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use chrono::NaiveDate;
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-use crate::lints::{Context, Error, Lint};
+use serde::Deserialize;
 
-#[derive(Debug)]
-pub struct Date<'n>(pub &'n str);
+use std::fmt::{Debug, Display};
 
-impl<'n> Lint for Date<'n> {
+#[derive(Debug, Deserialize)]
+pub struct Date<S>(pub S);
+
+impl<S> Lint for Date<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.0.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
-        let value = field.value().trim();
-
-        let mut error = None;
-
-        let lengths: Vec<_> = value.split('-').map(str::len).collect();
-        if lengths != [4, 2, 2] {
-            error = Some("invalid length".to_string());
-        }
-
-        if let Err(e) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
-            error = Some(e.to_string());
-        }
+        let item = field.date();
 
-        let slice_label = match error {
-            Some(e) => e,
-            None => return Ok(()),
+        let slice_label = match item.value() {
+            Ok(_) => return Ok(()),
+            Err(e) => e,
         };
 
         let label = format!(
@@ -57,11 +51,8 @@ impl<'n> Lint for Date<'n> {
                 source: field.source(),
                 annotations: vec![SourceAnnotation {
                     annotation_type: AnnotationType::Error,
-                    label: &slice_label,
-                    range: (
-                        field.name().len() + 1,
-                        field.value().len() + field.name().len() + 1,
-                    ),
+                    label: slice_label,
+                    range: item.range(),
                 }],
             }],
             opt: Default::default(),
@@ -69,4 +60,19 @@ impl<'n> Lint for Date<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "The `{}` header is a date in the `YYYY-MM-DD` format.",
+                self.0
+            ),
+            rationale: "A single date format keeps the headers sortable and unambiguous.".into(),
+            parameters: vec![Parameter::new("name", &self.0)],
+            examples: vec![
+                Example::pass(format!("---\n{}: 2022-01-31\n---\n", self.0)),
+                Example::fail(format!("---\n{}: 2022-1-31\n---\n", self.0)),
+            ],
+        }
+    }
 }
@@ -5,77 +5,105 @@
  */
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
-use regex::RegexSet;
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::preamble::address;
+
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
 
 fn footer() -> Vec<Annotation<'static>> {
-    vec![
-        Annotation {
-            annotation_type: AnnotationType::Help,
-            id: None,
-            label: Some("test@example.com"),
-        }
-    ]
+    vec![Annotation {
+        annotation_type: AnnotationType::Help,
+        id: None,
+        label: Some("test@example.com"),
+    }]
 }
 
-#[derive(Debug)]
-pub struct Email<'n>(pub &'n str);
+/// The `name` header is a single email address.
+///
+/// With `reject_disposable`, addresses from known providers of throwaway
+/// inboxes are reported too.
+#[derive(Debug, Deserialize)]
+pub struct Email<S> {
+    pub name: S,
+    #[serde(default)]
+    pub reject_disposable: bool,
+}
 
-impl<'n> Lint for Email<'n> {
+impl<S> Lint for Email<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.name.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
-        // TODO: Email addresses are insane, and can probably contain commas,
-        //       parentheses, and greater-/less- than symbols. For correctness,
-        //       we should switch to a parser that can handle those cases.
-
-        let item = field.value();
-
-        let set = RegexSet::new(&[
-            r"^[^@][^>]*@[^>]+\.[^>]+$", // Match an email address.
-        ])
-        .unwrap();
-
-        let offset = 0;
+        let value = field.value();
+        let trimmed = value.trim();
+        let offset = value.len() - value.trim_start().len();
 
+        let (title, label, range, footer) = match address::parse(trimmed) {
+            Ok(a) if self.reject_disposable && a.is_disposable() => (
+                "email must not be from a disposable email provider",
+                "disposable email domain",
+                trimmed.len() - a.domain.len()..trimmed.len(),
+                vec![],
+            ),
+            Ok(_) => return Ok(()),
+            Err(e) => (
+                "email must match the expected format",
+                e.label,
+                e.range,
+                footer(),
+            ),
+        };
 
-        let current = offset;
-        let trimmed = item.trim();
+        // Annotations count characters, not bytes.
+        let before = field.name().len() + 1 + value[..offset].chars().count();
+        let start = before + trimmed[..range.start].chars().count();
+        let end = before + trimmed[..range.end].chars().count();
 
-        let matches = set.matches(trimmed);
-        if matches.matched_any() == false {
-            ctx.report(Snippet {
-                title: Some(Annotation {
+        ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(slug),
+                label: Some(title),
+            }),
+            slices: vec![Slice {
+                fold: false,
+                line_start: field.line_start(),
+                origin: ctx.origin(),
+                source: field.source(),
+                annotations: vec![SourceAnnotation {
                     annotation_type: AnnotationType::Error,
-                    id: Some(slug),
-                    label: Some("email must match the expected format"),
-                }),
-                slices: vec![Slice {
-                    fold: false,
-                    line_start: field.line_start(),
-                    origin: ctx.origin(),
-                    source: field.source(),
-                    annotations: vec![SourceAnnotation {
-                        annotation_type: AnnotationType::Error,
-                        label: "unrecognized email",
-                        range: (
-                            field.name().len() + current + 1,
-                            field.name().len() + current + 1 + item.len(),
-                        ),
-                    }],
+                    label,
+                    range: (start, end),
                 }],
-                footer: footer(),
-                opt: Default::default(),
-            })?;
-        }
-        
-        
-
+            }],
+            footer,
+            opt: Default::default(),
+        })?;
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!("The `{}` header is an email address.", self.name),
+            rationale: "Applicants must be reachable by email.".into(),
+            parameters: vec![
+                Parameter::new("name", &self.name),
+                Parameter::new("reject_disposable", self.reject_disposable),
+            ],
+            examples: vec![
+                Example::pass(format!("---\n{}: test@example.com\n---\n", self.name)),
+                Example::fail(format!("---\n{}: test.example.com\n---\n", self.name)),
+                Example::fail(format!("---\n{}: test@example\n---\n", self.name)),
+            ],
+        }
+    }
 }
@@ -6,20 +6,26 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
 use std::path::Path;
 
-#[derive(Debug)]
-pub struct FileName<'n> {
-    pub name: &'n str,
-    pub prefix: &'n str,
-    pub suffix: &'n str,
+#[derive(Debug, Deserialize)]
+pub struct FileName<S> {
+    pub name: S,
+    pub prefix: S,
+    pub suffix: S,
 }
 
-impl<'n> Lint for FileName<'n> {
+impl<S> Lint for FileName<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.name) {
+        let field = match ctx.preamble().by_name(self.name.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
@@ -83,4 +89,25 @@ impl<'n> Lint for FileName<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let source = format!("---\n{}: 1\n---\n", self.name);
+
+        Metadata {
+            summary: format!("The file name reflects the `{}` header.", self.name),
+            rationale:
+                "Applications are looked up by file name, so it must match the number inside."
+                    .into(),
+            parameters: vec![
+                Parameter::new("name", &self.name),
+                Parameter::new("prefix", &self.prefix),
+                Parameter::new("suffix", &self.suffix),
+            ],
+            examples: vec![
+                Example::pass(source.clone())
+                    .with_origin(format!("{}0001{}", self.prefix, self.suffix)),
+                Example::fail(source).with_origin(format!("{}0002{}", self.prefix, self.suffix)),
+            ],
+        }
+    }
 }
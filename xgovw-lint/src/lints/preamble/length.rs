@@ -6,18 +6,25 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug)]
-pub struct Length<'n> {
-    pub name: &'n str,
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct Length<S> {
+    pub name: S,
     pub min: Option<usize>,
     pub max: Option<usize>,
 }
 
-impl<'n> Lint for Length<'n> {
+impl<S> Lint for Length<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.name) {
+        let field = match ctx.preamble().by_name(self.name.as_ref()) {
             None => return Ok(()),
             Some(f) => f,
         };
@@ -98,4 +105,27 @@ impl<'n> Lint for Length<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |len: usize| format!("---\n{}: {}\n---\n", self.name, "A".repeat(len));
+
+        let mut examples = vec![Example::pass(example(self.min.or(self.max).unwrap_or(1)))];
+
+        match (self.min, self.max) {
+            (_, Some(max)) => examples.push(Example::fail(example(max + 1))),
+            (Some(min), None) if min > 0 => examples.push(Example::fail(example(min - 1))),
+            _ => (),
+        }
+
+        Metadata {
+            summary: format!("The `{}` header has an acceptable length.", self.name),
+            rationale: "Values outside of the expected length are almost always typos.".into(),
+            parameters: vec![
+                Parameter::new("name", &self.name),
+                Parameter::new("min", self.min),
+                Parameter::new("max", self.max),
+            ],
+            examples,
+        }
+    }
 }
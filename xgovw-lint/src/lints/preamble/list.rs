@@ -6,125 +6,150 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+use crate::preamble::{Field, Item};
 
-#[derive(Debug)]
-pub struct List<'n>(pub &'n str);
+use serde::Deserialize;
 
-impl<'n> Lint for List<'n> {
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct List<S>(pub S);
+
+impl<S> Lint for List<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.0.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
-        let mut missing_space = Vec::new();
-        let mut extra_space = Vec::new();
+        separators(slug, ctx, field, &field.list())
+    }
 
-        let value = field.value().trim();
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "The `{}` header is a correctly formatted comma-separated list.",
+                self.0
+            ),
+            rationale: "Consistent separators make the list easy to read and to parse.".into(),
+            parameters: vec![Parameter::new("name", &self.0)],
+            examples: vec![
+                Example::pass(format!("---\n{}: a, b\n---\n", self.0)),
+                Example::fail(format!("---\n{}: a,b\n---\n", self.0)),
+            ],
+        }
+    }
+}
 
-        let mut offset = 0;
-        for matched in value.split(',') {
-            let current = offset;
-            offset += matched.len() + 1;
+/// Checks the commas and spaces between `items`, which were split out of
+/// `field`.
+pub(super) fn separators<'a>(
+    slug: &'a str,
+    ctx: &Context<'a, '_>,
+    field: Field<'a>,
+    items: &[Item<'a, &'a str>],
+) -> Result<(), Error> {
+    let mut missing_space = Vec::new();
+    let mut extra_space = Vec::new();
 
-            let trimmed = matched.trim();
-            if trimmed.is_empty() {
-                let label = format!("preamble header `{}` cannot have empty items", self.0);
-                ctx.report(Snippet {
-                    title: Some(Annotation {
-                        annotation_type: AnnotationType::Error,
-                        id: Some(slug),
-                        label: Some(&label),
-                    }),
-                    footer: vec![],
-                    slices: vec![Slice {
-                        fold: false,
-                        line_start: field.line_start(),
-                        origin: ctx.origin(),
-                        source: field.source(),
-                        annotations: vec![SourceAnnotation {
-                            annotation_type: AnnotationType::Error,
-                            label: "this item is empty",
-                            range: (
-                                field.name().len() + current + 1,
-                                field.name().len() + current + 2,
-                            ),
-                        }],
-                    }],
-                    opt: Default::default(),
-                })?;
-                continue;
-            }
-
-            let rest = match matched.strip_prefix(' ') {
-                Some(r) => r,
-                None if current == 0 => matched,
-                None => {
-                    missing_space.push(SourceAnnotation {
-                        annotation_type: AnnotationType::Error,
-                        label: "missing space",
-                        range: (
-                            field.name().len() + current + 1,
-                            field.name().len() + current + 2,
-                        ),
-                    });
-                    continue;
-                }
-            };
-
-            if rest.trim() == rest {
-                continue;
-            }
+    let last = items.len() - 1;
 
-            extra_space.push(SourceAnnotation {
-                annotation_type: AnnotationType::Error,
-                label: "extra space",
-                range: (
-                    field.name().len() + current + 2,
-                    field.name().len() + current + 2 + matched.len(),
-                ),
-            });
-        }
+    for (index, item) in items.iter().enumerate() {
+        let (start, end) = item.range();
+        let (padded_start, padded_end) = item.padded_range();
+
+        if item.text().is_empty() {
+            // An empty last item has nothing to point at, so point at the
+            // comma before it instead.
+            let at = padded_start.min(field.source().chars().count() - 1);
 
-        if !missing_space.is_empty() {
+            let label = format!("preamble header `{}` cannot have empty items", field.name());
             ctx.report(Snippet {
                 title: Some(Annotation {
                     annotation_type: AnnotationType::Error,
                     id: Some(slug),
-                    label: Some("preamble header list items must begin with a space"),
+                    label: Some(&label),
                 }),
                 footer: vec![],
                 slices: vec![Slice {
-                    line_start: field.line_start(),
                     fold: false,
+                    line_start: field.line_start(),
                     origin: ctx.origin(),
                     source: field.source(),
-                    annotations: missing_space,
+                    annotations: vec![SourceAnnotation {
+                        annotation_type: AnnotationType::Error,
+                        label: "this item is empty",
+                        range: (at, at + 1),
+                    }],
                 }],
                 opt: Default::default(),
             })?;
+            continue;
         }
 
-        if !extra_space.is_empty() {
-            ctx.report(Snippet {
-                title: Some(Annotation {
-                    annotation_type: AnnotationType::Error,
-                    id: Some(slug),
-                    label: Some("preamble header list items have extra whitespace"),
-                }),
-                footer: vec![],
-                slices: vec![Slice {
-                    line_start: field.line_start(),
-                    fold: false,
-                    origin: ctx.origin(),
-                    source: field.source(),
-                    annotations: extra_space,
-                }],
-                opt: Default::default(),
-            })?;
+        // Whitespace before the first item and after the last is checked
+        // by `preamble::Trim`.
+        let leading = if index == 0 { 1 } else { start - padded_start };
+        let trailing = if index == last { 0 } else { padded_end - end };
+
+        if leading == 0 {
+            missing_space.push(SourceAnnotation {
+                annotation_type: AnnotationType::Error,
+                label: "missing space",
+                range: (start, start + 1),
+            });
         }
 
-        Ok(())
+        if leading > 1 || trailing > 0 {
+            extra_space.push(SourceAnnotation {
+                annotation_type: AnnotationType::Error,
+                label: "extra space",
+                range: item.padded_range(),
+            });
+        }
+    }
+
+    if !missing_space.is_empty() {
+        ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(slug),
+                label: Some("preamble header list items must begin with a space"),
+            }),
+            footer: vec![],
+            slices: vec![Slice {
+                line_start: field.line_start(),
+                fold: false,
+                origin: ctx.origin(),
+                source: field.source(),
+                annotations: missing_space,
+            }],
+            opt: Default::default(),
+        })?;
+    }
+
+    if !extra_space.is_empty() {
+        ctx.report(Snippet {
+            title: Some(Annotation {
+                annotation_type: AnnotationType::Error,
+                id: Some(slug),
+                label: Some("preamble header list items have extra whitespace"),
+            }),
+            footer: vec![],
+            slices: vec![Slice {
+                line_start: field.line_start(),
+                fold: false,
+                origin: ctx.origin(),
+                source: field.source(),
+                annotations: extra_space,
+            }],
+            opt: Default::default(),
+        })?;
     }
+
+    Ok(())
 }
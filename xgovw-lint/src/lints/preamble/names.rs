@@ -0,0 +1,87 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Matching misspelled preamble header names to the names they were meant to be.
+
+use crate::preamble::{Field, Preamble};
+
+/// Pairs each field whose name isn't in `names` with the closest name in
+/// `names` that doesn't appear in the preamble, if there is one close enough.
+///
+/// Each name is claimed by at most one field, in the order the fields appear.
+pub(crate) fn misspellings<'a, 'n>(
+    preamble: &Preamble<'a>,
+    names: &[&'n str],
+) -> Vec<(Field<'a>, Option<&'n str>)> {
+    let mut absent: Vec<_> = names
+        .iter()
+        .copied()
+        .filter(|n| preamble.by_name(n).is_none())
+        .collect();
+
+    preamble
+        .fields()
+        .filter(|f| !names.contains(&f.name()))
+        .map(|field| {
+            let closest = closest(field.name(), &absent);
+            if let Some(name) = closest {
+                absent.retain(|n| *n != name);
+            }
+            (field, closest)
+        })
+        .collect()
+}
+
+fn closest<'n>(name: &str, candidates: &[&'n str]) -> Option<&'n str> {
+    let name = normalize(name);
+
+    candidates
+        .iter()
+        .map(|candidate| {
+            let normal = normalize(candidate);
+            let threshold = (normal.chars().count() / 3).max(1);
+            let distance = strsim::damerau_levenshtein(&name, &normal);
+            (distance, threshold, *candidate)
+        })
+        .filter(|(distance, threshold, _)| distance <= threshold)
+        .min_by_key(|(distance, _, _)| *distance)
+        .map(|(_, _, candidate)| candidate)
+}
+
+/// Lowercases `name` and removes separators, so `E-Mail` becomes `email`.
+fn normalize(name: &str) -> String {
+    name.trim()
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn misspellings_claim_absent_names() {
+        let preamble =
+            Preamble::parse(None, "id: 1\nadress: x\ne-mail: y\nemial: z\ncolor: red").unwrap();
+
+        let found: Vec<_> = misspellings(&preamble, &["id", "email", "address"])
+            .into_iter()
+            .map(|(f, n)| (f.name(), n))
+            .collect();
+
+        assert_eq!(
+            found,
+            [
+                ("adress", Some("address")),
+                ("e-mail", Some("email")),
+                ("emial", None),
+                ("color", None),
+            ]
+        );
+    }
+}
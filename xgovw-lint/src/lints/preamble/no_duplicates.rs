@@ -6,11 +6,13 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata};
+
+use serde::Deserialize;
 
 use std::collections::hash_map::{Entry, HashMap};
 
-#[derive(Debug)]
+#[derive(Debug, Deserialize)]
 pub struct NoDuplicates;
 
 impl Lint for NoDuplicates {
@@ -67,4 +69,16 @@ impl Lint for NoDuplicates {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "There are no duplicate headers.".into(),
+            rationale: "When a header appears twice, it is unclear which value is meant.".into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n"),
+                Example::fail("---\nid: 1\nid: 2\n---\n"),
+            ],
+        }
+    }
 }
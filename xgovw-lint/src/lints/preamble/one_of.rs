@@ -6,28 +6,37 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug)]
-pub struct OneOf<'n> {
-    pub name: &'n str,
-    pub values: &'n [&'n str],
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct OneOf<S> {
+    pub name: S,
+    pub values: Vec<S>,
 }
 
-impl<'n> Lint for OneOf<'n> {
+impl<S> Lint for OneOf<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.name) {
+        let field = match ctx.preamble().by_name(self.name.as_ref()) {
             None => return Ok(()),
             Some(f) => f,
         };
 
-        if self.values.contains(&field.value().trim()) {
+        let value = field.value().trim();
+        if self.values.iter().any(|v| v.as_ref() == value) {
             return Ok(());
         }
 
         let label = format!("preamble header `{}` has an unrecognized value", self.name);
 
-        let slice_label = format!("must be one of: `{}`", self.values.join("`, `"));
+        let values: Vec<_> = self.values.iter().map(AsRef::as_ref).collect();
+        let slice_label = format!("must be one of: `{}`", values.join("`, `"));
 
         ctx.report(Snippet {
             title: Some(Annotation {
@@ -55,4 +64,28 @@ impl<'n> Lint for OneOf<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |value: &str| format!("---\n{}: {}\n---\n", self.name, value);
+
+        let mut examples = vec![];
+
+        if let Some(first) = self.values.first() {
+            examples.push(Example::pass(example(first.as_ref())));
+        }
+
+        if !self.values.iter().any(|v| v.as_ref() == "<unrecognized>") {
+            examples.push(Example::fail(example("<unrecognized>")));
+        }
+
+        Metadata {
+            summary: format!("The `{}` header is a recognized value.", self.name),
+            rationale: "Only a fixed set of values has a meaning to the xGov process.".into(),
+            parameters: vec![
+                Parameter::new("name", &self.name),
+                Parameter::new("values", &self.values),
+            ],
+            examples,
+        }
+    }
 }
@@ -4,24 +4,32 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
-use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
+use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-use std::fmt::Write;
+use serde::Deserialize;
 
-#[derive(Debug)]
-pub struct Order<'n>(pub &'n [&'n str]);
+use std::fmt::{Debug, Display, Write};
 
-impl<'n> Order<'n> {
+/// The preamble headers in `names` appear in that order. Other headers are
+/// left to [`Allowed`](super::Allowed).
+#[derive(Debug, Deserialize)]
+pub struct Order<S>(pub Vec<S>);
+
+impl<S> Order<S>
+where
+    S: AsRef<str>,
+{
     fn find_preceding(&self, present: &[&str], needle: &str) -> Option<&str> {
-        let needle_idx = match self.0.iter().position(|x| *x == needle) {
+        let needle_idx = match self.0.iter().position(|x| x.as_ref() == needle) {
             None | Some(0) => return None,
             Some(i) => i,
         };
 
         for (idx, name) in self.0.iter().enumerate().rev() {
-            if *name != needle && present.contains(name) && idx < needle_idx {
+            let name = name.as_ref();
+            if name != needle && present.contains(&name) && idx < needle_idx {
                 return Some(name);
             }
         }
@@ -30,45 +38,17 @@ impl<'n> Order<'n> {
     }
 }
 
-impl<'n> Lint for Order<'n> {
+impl<S> Lint for Order<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        // Check for unknown headers.
-        let unknowns: Vec<_> = ctx
-            .preamble()
-            .fields()
-            .filter(|f| !self.0.contains(&f.name()))
-            .map(|f| Slice {
-                line_start: f.line_start(),
-                fold: false,
-                origin: ctx.origin(),
-                source: f.source(),
-                annotations: vec![SourceAnnotation {
-                    annotation_type: AnnotationType::Error,
-                    label: "unrecognized header",
-                    range: (0, f.name().len()),
-                }],
-            })
-            .collect();
-
-        if !unknowns.is_empty() {
-            ctx.report(Snippet {
-                title: Some(Annotation {
-                    id: Some(slug),
-                    annotation_type: AnnotationType::Error,
-                    label: Some("preamble has extra header(s)"),
-                }),
-                footer: vec![],
-                slices: unknowns,
-                opt: Default::default(),
-            })?;
-        }
-
         let present: Vec<_> = ctx.preamble().fields().map(|f| f.name()).collect();
 
         // Check that headers are in the correct order.
         let mut max_line = 0;
         for name in self.0.iter() {
-            if let Some(field) = ctx.preamble().by_name(name) {
+            if let Some(field) = ctx.preamble().by_name(name.as_ref()) {
                 let cur = max_line;
                 max_line = field.line_start();
 
@@ -117,4 +97,32 @@ impl<'n> Lint for Order<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |names: &[&str]| {
+            let fields: String = names.iter().map(|n| format!("{}: value\n", n)).collect();
+            format!("---\n{}---\n", fields)
+        };
+
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        let mut examples = vec![];
+
+        if !names.is_empty() {
+            examples.push(Example::pass(example(&names)));
+        }
+
+        if names.len() >= 2 {
+            let mut swapped = names.clone();
+            swapped.swap(0, 1);
+            examples.push(Example::fail(example(&swapped)));
+        }
+
+        Metadata {
+            summary: "The preamble headers are in the correct order.".into(),
+            rationale: "A consistent order makes applications easy to compare side by side.".into(),
+            parameters: vec![Parameter::new("names", &self.0)],
+            examples,
+        }
+    }
 }
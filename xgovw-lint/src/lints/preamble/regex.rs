@@ -6,33 +6,41 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Mode {
     Includes,
     Excludes,
 }
 
-#[derive(Debug)]
-pub struct Regex<'n> {
-    pub name: &'n str,
+#[derive(Debug, Deserialize)]
+pub struct Regex<S> {
+    pub name: S,
     pub mode: Mode,
-    pub pattern: &'n str,
-    pub message: &'n str,
+    pub pattern: S,
+    pub message: S,
 }
 
-impl<'n> Lint for Regex<'n> {
+impl<S> Lint for Regex<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.name) {
+        let field = match ctx.preamble().by_name(self.name.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
         let value = field.value().trim();
 
-        let re = ::regex::Regex::new(self.pattern).map_err(Error::custom)?;
+        let re = ::regex::Regex::new(self.pattern.as_ref()).map_err(Error::custom)?;
         let matches = re.is_match(value);
 
         let slice_label = match (self.mode, matches) {
@@ -52,7 +60,7 @@ impl<'n> Lint for Regex<'n> {
             title: Some(Annotation {
                 annotation_type: AnnotationType::Error,
                 id: Some(slug),
-                label: Some(self.message),
+                label: Some(self.message.as_ref()),
             }),
             slices: vec![Slice {
                 fold: false,
@@ -78,4 +86,45 @@ impl<'n> Lint for Regex<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let pattern = self.pattern.as_ref();
+        let example = |value: &str| format!("---\n{}: {}\n---\n", self.name, value);
+
+        let mut examples = vec![];
+
+        // A value that matches is only known when the pattern matches itself.
+        if let Ok(re) = ::regex::Regex::new(pattern) {
+            let matching = Some(pattern).filter(|p| re.is_match(p));
+            let other = Some("value").filter(|v| !re.is_match(v));
+
+            let (pass, fail) = match self.mode {
+                Mode::Includes => (matching, other),
+                Mode::Excludes => (other, matching),
+            };
+
+            examples.extend(pass.map(|v| Example::pass(example(v))));
+            examples.extend(fail.map(|v| Example::fail(example(v))));
+        }
+
+        Metadata {
+            summary: format!(
+                "The `{}` header {} the pattern `{}`.",
+                self.name,
+                match self.mode {
+                    Mode::Includes => "matches",
+                    Mode::Excludes => "does not match",
+                },
+                self.pattern,
+            ),
+            rationale: self.message.to_string(),
+            parameters: vec![
+                Parameter::new("name", &self.name),
+                Parameter::new("mode", self.mode),
+                Parameter::new("pattern", &self.pattern),
+                Parameter::new("message", &self.message),
+            ],
+            examples,
+        }
+    }
 }
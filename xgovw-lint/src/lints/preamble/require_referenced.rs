@@ -6,34 +6,37 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
 
 use regex::Regex;
 
-#[derive(Debug)]
-pub struct RequireReferenced<'n> {
-    pub name: &'n str,
-    pub requires: &'n str,
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct RequireReferenced<S> {
+    pub name: S,
+    pub requires: S,
 }
 
-impl<'n> Lint for RequireReferenced<'n> {
+impl<S> Lint for RequireReferenced<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.name) {
+        let field = match ctx.preamble().by_name(self.name.as_ref()) {
             None => return Ok(()),
             Some(f) => f,
         };
 
-        let requires_txt = ctx
+        let requires: Vec<_> = ctx
             .preamble()
-            .by_name(self.requires)
-            .map(|f| f.value())
-            .unwrap_or_default();
-
-        let requires: Vec<_> = requires_txt
-            .split(',')
-            .map(str::trim)
-            .map(str::parse::<u64>)
-            .filter_map(Result::ok)
+            .by_name(self.requires.as_ref())
+            .map(|f| f.uints())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|i| i.into_value().ok())
             .collect();
 
         let re = Regex::new(r"(?i)xGov-([0-9]+)").unwrap();
@@ -62,10 +65,7 @@ impl<'n> Lint for RequireReferenced<'n> {
         let annotations = missing
             .iter()
             .map(|m| SourceAnnotation {
-                range: (
-                    m.start() + field.name().len() + 1,
-                    m.end() + field.name().len() + 1,
-                ),
+                range: field.annotation_range(m.range()),
                 label: "mentioned here",
                 annotation_type: AnnotationType::Error,
             })
@@ -89,4 +89,25 @@ impl<'n> Lint for RequireReferenced<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "Proposals mentioned in the `{}` header are listed in `{}`.",
+                self.name, self.requires
+            ),
+            rationale: "Every dependency on another proposal must be declared explicitly.".into(),
+            parameters: vec![
+                Parameter::new("name", &self.name),
+                Parameter::new("requires", &self.requires),
+            ],
+            examples: vec![
+                Example::pass(format!(
+                    "---\n{}: see xGov-1\n{}: 1\n---\n",
+                    self.name, self.requires
+                )),
+                Example::fail(format!("---\n{}: see xGov-1\n---\n", self.name)),
+            ],
+        }
+    }
 }
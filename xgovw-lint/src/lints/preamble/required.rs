@@ -6,18 +6,36 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug)]
-pub struct Required<'n>(pub &'n [&'n str]);
+use serde::Deserialize;
 
-impl<'n> Lint for Required<'n> {
+use std::fmt::{Debug, Display};
+
+use super::names;
+
+#[derive(Debug, Deserialize)]
+pub struct Required<S>(pub Vec<S>);
+
+impl<S> Lint for Required<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let missing = self
-            .0
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        // Names that look misspelled are reported by `Allowed`, together with
+        // the header that was probably meant to be them.
+        let misspelled: Vec<_> = names::misspellings(ctx.preamble(), &names)
+            .into_iter()
+            .filter_map(|(_, name)| name)
+            .collect();
+
+        let missing = names
             .iter()
+            .copied()
             .filter(|name| ctx.preamble().by_name(name).is_none())
-            .cloned()
+            .filter(|name| !misspelled.contains(name))
             .collect::<Vec<_>>()
             .join("`, `");
 
@@ -43,4 +61,30 @@ impl<'n> Lint for Required<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |names: &[&str]| {
+            let fields: String = names.iter().map(|n| format!("{}: value\n", n)).collect();
+            format!("---\n{}---\n", fields)
+        };
+
+        let names: Vec<_> = self.0.iter().map(AsRef::as_ref).collect();
+
+        let mut examples = vec![];
+
+        if !names.is_empty() {
+            examples.push(Example::pass(example(&names)));
+        }
+
+        if names.len() >= 2 {
+            examples.push(Example::fail(example(&names[..names.len() - 1])));
+        }
+
+        Metadata {
+            summary: "All required preamble headers are present.".into(),
+            rationale: "Reviewers rely on these headers to process an application.".into(),
+            parameters: vec![Parameter::new("names", &self.0)],
+            examples,
+        }
+    }
 }
@@ -6,29 +6,36 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug)]
-pub struct RequiredIfEq<'b> {
-    pub when: &'b str,
-    pub equals: &'b str,
-    pub then: &'b str,
+use serde::Deserialize;
+
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct RequiredIfEq<S> {
+    pub when: S,
+    pub equals: S,
+    pub then: S,
 }
 
-impl<'n> Lint for RequiredIfEq<'n> {
+impl<S> Lint for RequiredIfEq<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let then_opt = ctx.preamble().by_name(self.then);
-        let when_opt = ctx.preamble().by_name(self.when);
+        let then_opt = ctx.preamble().by_name(self.then.as_ref());
+        let when_opt = ctx.preamble().by_name(self.when.as_ref());
 
         match (when_opt, then_opt) {
             // Correct.
             (None, None) => (),
 
             // Correct.
-            (Some(when), Some(_)) if when.value().trim() == self.equals => (),
+            (Some(when), Some(_)) if when.value().trim() == self.equals.as_ref() => (),
 
             // Correct.
-            (Some(when), None) if when.value().trim() != self.equals => (),
+            (Some(when), None) if when.value().trim() != self.equals.as_ref() => (),
 
             // Incorrect.
             (Some(when), None) => {
@@ -138,4 +145,29 @@ impl<'n> Lint for RequiredIfEq<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "The `{}` header is present if and only if `{}` is `{}`.",
+                self.then, self.when, self.equals
+            ),
+            rationale: format!(
+                "`{}` is only meaningful when `{}` is `{}`.",
+                self.then, self.when, self.equals
+            ),
+            parameters: vec![
+                Parameter::new("when", &self.when),
+                Parameter::new("equals", &self.equals),
+                Parameter::new("then", &self.then),
+            ],
+            examples: vec![
+                Example::pass(format!(
+                    "---\n{}: {}\n{}: value\n---\n",
+                    self.when, self.equals, self.then
+                )),
+                Example::fail(format!("---\n{}: {}\n---\n", self.when, self.equals)),
+            ],
+        }
+    }
 }
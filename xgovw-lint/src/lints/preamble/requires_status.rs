@@ -6,43 +6,49 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, FetchContext, Lint};
+use crate::lints::{Context, Error, Example, FetchContext, Lint, Metadata, Parameter};
+
+use serde::Deserialize;
 
 use std::collections::HashMap;
+use std::fmt::{Debug, Display};
 use std::path::PathBuf;
 
-#[derive(Debug)]
-pub struct RequiresStatus<'n> {
-    pub requires: &'n str,
-    pub status: &'n str,
-    pub flow: &'n [&'n [&'n str]],
+#[derive(Debug, Deserialize)]
+pub struct RequiresStatus<S> {
+    pub requires: S,
+    pub status: S,
+    pub flow: Vec<Vec<S>>,
 }
 
-impl<'n> RequiresStatus<'n> {
+impl<S> RequiresStatus<S>
+where
+    S: AsRef<str>,
+{
     fn tier(&self, map: &HashMap<&str, usize>, ctx: &Context<'_, '_>) -> usize {
         ctx.preamble()
-            .by_name(self.status)
-            .map(|f| f.value())
-            .map(str::trim)
+            .by_name(self.status.as_ref())
+            .map(|f| f.string().into_value())
             .and_then(|s| map.get(s))
             .copied()
             .unwrap_or(0)
     }
 }
 
-impl<'n> Lint for RequiresStatus<'n> {
+impl<S> Lint for RequiresStatus<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn find_resources<'a>(&self, ctx: &FetchContext<'a>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.requires) {
+        let field = match ctx.preamble().by_name(self.requires.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
         field
-            .value()
-            .split(',')
-            .map(str::trim)
-            .map(str::parse::<u64>)
-            .filter_map(Result::ok)
+            .uints()
+            .into_iter()
+            .filter_map(|i| i.into_value().ok())
             .map(|n| {
                 let mut number = format!("xgov-{}.md", n);
                 if n < 10 {
@@ -61,15 +67,15 @@ impl<'n> Lint for RequiresStatus<'n> {
     }
 
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.requires) {
+        let field = match ctx.preamble().by_name(self.requires.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
         let mut map = HashMap::new();
         for (tier, values) in self.flow.iter().enumerate() {
-            for value in *values {
-                map.insert(*value, tier + 1);
+            for value in values {
+                map.insert(value.as_ref(), tier + 1);
             }
         }
 
@@ -77,14 +83,8 @@ impl<'n> Lint for RequiresStatus<'n> {
         let mut too_unstable = Vec::new();
         let mut min = usize::MAX;
 
-        let items = field.value().split(',');
-
-        let mut offset = 0;
-        for item in items {
-            let current = offset;
-            offset += item.len() + 1;
-
-            let key = match item.trim().parse::<u64>() {
+        for item in field.uints() {
+            let key = match *item.value() {
                 Ok(k) => {
                     let mut number = format!("xgov-{}.md", k);
                     if k < 10 {
@@ -98,7 +98,7 @@ impl<'n> Lint for RequiresStatus<'n> {
                 }
                 _ => continue,
             };
-            let xgov = match ctx.xgov(&key) {
+            let xgov = match ctx.xgov(&key)? {
                 Ok(xgov) => xgov,
                 Err(e) => {
                     let label = format!("unable to read file `{}`: {}", key.display(), e);
@@ -116,10 +116,7 @@ impl<'n> Lint for RequiresStatus<'n> {
                             annotations: vec![SourceAnnotation {
                                 annotation_type: AnnotationType::Error,
                                 label: "required from here",
-                                range: (
-                                    field.name().len() + current + 1,
-                                    field.name().len() + current + 1 + item.len(),
-                                ),
+                                range: item.range(),
                             }],
                         }],
                         ..Default::default()
@@ -140,10 +137,7 @@ impl<'n> Lint for RequiresStatus<'n> {
             too_unstable.push(SourceAnnotation {
                 annotation_type: AnnotationType::Error,
                 label: "has a less advanced status",
-                range: (
-                    field.name().len() + current + 1,
-                    field.name().len() + current + 1 + item.len(),
-                ),
+                range: item.range(),
             });
         }
 
@@ -153,10 +147,9 @@ impl<'n> Lint for RequiresStatus<'n> {
                 self.requires,
                 self.status,
                 ctx.preamble()
-                    .by_name(self.status)
-                    .map(|f| f.value())
-                    .unwrap_or("<missing>")
-                    .trim(),
+                    .by_name(self.status.as_ref())
+                    .map(|f| f.string().into_value())
+                    .unwrap_or("<missing>"),
             );
 
             let mut choices = map
@@ -202,4 +195,50 @@ impl<'n> Lint for RequiresStatus<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        let example = |mine: &str, theirs: &str| {
+            let source = format!(
+                "---\n{0}: 1\n{1}: {2}\n---\n",
+                self.requires, self.status, mine
+            );
+            let required = format!("---\n{}: {}\n---\n", self.status, theirs);
+            (source, required)
+        };
+
+        let first = self.flow.first().and_then(|t| t.first());
+        let last = self.flow.last().and_then(|t| t.first());
+
+        let mut examples = vec![];
+
+        if let (Some(first), Some(last), true) = (first, last, self.flow.len() >= 2) {
+            let (source, required) = example(first.as_ref(), last.as_ref());
+            examples.push(
+                Example::pass(source)
+                    .with_origin("xgov-0002.md")
+                    .with_file("xgov-0001.md", required),
+            );
+
+            let (source, required) = example(last.as_ref(), first.as_ref());
+            examples.push(
+                Example::fail(source)
+                    .with_origin("xgov-0002.md")
+                    .with_file("xgov-0001.md", required),
+            );
+        }
+
+        Metadata {
+            summary: format!(
+                "Proposals listed in the `{}` header have a `{}` at least as advanced as this one.",
+                self.requires, self.status
+            ),
+            rationale: "A proposal cannot be more stable than the proposals it depends on.".into(),
+            parameters: vec![
+                Parameter::new("requires", &self.requires),
+                Parameter::new("status", &self.status),
+                Parameter::new("flow", &self.flow),
+            ],
+            examples,
+        }
+    }
 }
@@ -6,9 +6,11 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata};
 
-#[derive(Debug)]
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
 pub struct Trim;
 
 impl Lint for Trim {
@@ -87,4 +89,16 @@ impl Lint for Trim {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: "There is no extra whitespace around preamble fields.".into(),
+            rationale: "Stray whitespace produces noisy diffs and can break naive parsers.".into(),
+            parameters: vec![],
+            examples: vec![
+                Example::pass("---\nid: 1\n---\n"),
+                Example::fail("---\nid:1 \n---\n"),
+            ],
+        }
+    }
 }
@@ -6,19 +6,28 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug)]
-pub struct Uint<'n>(pub &'n str);
+use serde::Deserialize;
 
-impl<'n> Lint for Uint<'n> {
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct Uint<S>(pub S);
+
+impl<S> Lint for Uint<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.0.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
-        if field.value().trim().parse::<u64>().is_err() {
+        let item = field.uint();
+
+        if item.value().is_err() {
             let label = format!("preamble header `{}` must be an unsigned integer", self.0);
 
             ctx.report(Snippet {
@@ -35,10 +44,7 @@ impl<'n> Lint for Uint<'n> {
                     annotations: vec![SourceAnnotation {
                         annotation_type: AnnotationType::Error,
                         label: "not a non-negative integer",
-                        range: (
-                            field.name().len() + 1,
-                            field.value().len() + field.name().len() + 1,
-                        ),
+                        range: item.range(),
                     }],
                 }],
                 footer: vec![],
@@ -48,42 +54,44 @@ impl<'n> Lint for Uint<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!("The `{}` header is a non-negative integer.", self.0),
+            rationale: "The value is used as a number, so it must parse as one.".into(),
+            parameters: vec![Parameter::new("name", &self.0)],
+            examples: vec![
+                Example::pass(format!("---\n{}: 1\n---\n", self.0)),
+                Example::fail(format!("---\n{}: one\n---\n", self.0)),
+            ],
+        }
+    }
 }
 
-#[derive(Debug)]
-pub struct UintList<'n>(pub &'n str);
+#[derive(Debug, Deserialize)]
+pub struct UintList<S>(pub S);
 
-impl<'n> Lint for UintList<'n> {
+impl<S> Lint for UintList<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.0.as_ref()) {
             None => return Ok(()),
             Some(s) => s,
         };
 
-        let items = field.value().split(',');
         let mut values: Vec<u64> = Vec::new();
         let mut not_uint = Vec::new();
 
-        let mut offset = 0;
-
-        for item in items {
-            let current = offset;
-            offset += item.len() + 1;
-            let trimmed = item.trim();
-
-            match trimmed.parse() {
-                Ok(v) => values.push(v),
-                Err(_) => {
-                    not_uint.push(SourceAnnotation {
-                        annotation_type: AnnotationType::Error,
-                        label: "not a non-negative integer",
-                        range: (
-                            field.name().len() + current + 1,
-                            field.name().len() + current + 1 + item.len(),
-                        ),
-                    });
-                    continue;
-                }
+        for item in field.uints() {
+            match item.value() {
+                Ok(v) => values.push(*v),
+                Err(_) => not_uint.push(SourceAnnotation {
+                    annotation_type: AnnotationType::Error,
+                    label: "not a non-negative integer",
+                    range: item.range(),
+                }),
             }
         }
 
@@ -141,4 +149,19 @@ impl<'n> Lint for UintList<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!(
+                "The `{}` header is a sorted list of non-negative integers.",
+                self.0
+            ),
+            rationale: "Sorted numeric lists are easy to scan and to compare.".into(),
+            parameters: vec![Parameter::new("name", &self.0)],
+            examples: vec![
+                Example::pass(format!("---\n{}: 1, 2\n---\n", self.0)),
+                Example::fail(format!("---\n{}: 2, 1\n---\n", self.0)),
+            ],
+        }
+    }
 }
@@ -6,21 +6,28 @@
 
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet, SourceAnnotation};
 
-use crate::lints::{Context, Error, Lint};
+use crate::lints::{Context, Error, Example, Lint, Metadata, Parameter};
 
-#[derive(Debug)]
-pub struct Url<'n>(pub &'n str);
+use serde::Deserialize;
 
-impl<'n> Lint for Url<'n> {
+use std::fmt::{Debug, Display};
+
+#[derive(Debug, Deserialize)]
+pub struct Url<S>(pub S);
+
+impl<S> Lint for Url<S>
+where
+    S: Display + Debug + AsRef<str>,
+{
     fn lint<'a, 'b>(&self, slug: &'a str, ctx: &Context<'a, 'b>) -> Result<(), Error> {
-        let field = match ctx.preamble().by_name(self.0) {
+        let field = match ctx.preamble().by_name(self.0.as_ref()) {
             Some(f) => f,
             None => return Ok(()),
         };
 
-        let value = field.value().trim();
+        let item = field.url();
 
-        let e = match ::url::Url::parse(value) {
+        let e = match item.value() {
             Ok(_) => return Ok(()),
             Err(e) => e,
         };
@@ -43,10 +50,7 @@ impl<'n> Lint for Url<'n> {
                 annotations: vec![SourceAnnotation {
                     annotation_type: AnnotationType::Error,
                     label: &slice_label,
-                    range: (
-                        field.name().len() + 1,
-                        field.value().len() + field.name().len() + 1,
-                    ),
+                    range: item.range(),
                 }],
             }],
             opt: Default::default(),
@@ -54,4 +58,17 @@ impl<'n> Lint for Url<'n> {
 
         Ok(())
     }
+
+    fn metadata(&self) -> Metadata {
+        Metadata {
+            summary: format!("The `{}` header is an absolute URL.", self.0),
+            rationale: "Relative or malformed URLs cannot be followed from the rendered page."
+                .into(),
+            parameters: vec![Parameter::new("name", &self.0)],
+            examples: vec![
+                Example::pass(format!("---\n{}: https://example.com\n---\n", self.0)),
+                Example::fail(format!("---\n{}: example.com\n---\n", self.0)),
+            ],
+        }
+    }
 }
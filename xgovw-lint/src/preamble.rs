@@ -4,13 +4,24 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/.
  */
 
+pub(crate) mod address;
+pub mod author;
+
 use annotate_snippets::snippet::{Annotation, AnnotationType, Slice, Snippet};
 
+use chrono::NaiveDate;
+
 use regex::Regex;
 
 use snafu::{ensure, Backtrace, OptionExt, Snafu};
 
 use std::collections::HashMap;
+use std::num::ParseIntError;
+use std::ops::Range;
+
+use url::Url;
+
+use self::author::{InvalidAuthor, ParsedAuthor};
 
 #[derive(Debug, Snafu)]
 pub(crate) struct ParseErrors<'a> {
@@ -193,6 +204,155 @@ impl<'a> Field<'a> {
     pub fn source(&self) -> &'a str {
         self.source
     }
+
+    /// Converts a byte range in `value` into a range of characters in
+    /// `source`, for annotations.
+    pub fn annotation_range(&self, bytes: Range<usize>) -> (usize, usize) {
+        let offset = self.name.chars().count() + 1;
+        (
+            offset + self.value[..bytes.start].chars().count(),
+            offset + self.value[..bytes.end].chars().count(),
+        )
+    }
+
+    fn item<T, F>(&self, bytes: Range<usize>, parse: F) -> Item<'a, T>
+    where
+        F: FnOnce(&'a str) -> T,
+    {
+        let padded = &self.value[bytes.clone()];
+        let text = padded.trim();
+        let start = bytes.start + padded.len() - padded.trim_start().len();
+
+        Item {
+            value: parse(text),
+            text,
+            range: self.annotation_range(start..start + text.len()),
+            padded: self.annotation_range(bytes),
+        }
+    }
+
+    /// Splits `value` at commas, keeping the whitespace around each item.
+    fn split(&self) -> Vec<Range<usize>> {
+        let mut start = 0;
+        let mut ranges = vec![];
+
+        for item in self.value.split(',') {
+            ranges.push(start..start + item.len());
+            start += item.len() + 1;
+        }
+
+        ranges
+    }
+
+    /// The value, without whitespace around it.
+    pub fn string(&self) -> Item<'a, &'a str> {
+        self.item(0..self.value.len(), |t| t)
+    }
+
+    /// The value as a non-negative integer.
+    pub fn uint(&self) -> Item<'a, Result<u64, ParseIntError>> {
+        self.item(0..self.value.len(), str::parse)
+    }
+
+    /// The value as a date in the `YYYY-MM-DD` format. Errors are suitable
+    /// for labels.
+    pub fn date(&self) -> Item<'a, Result<NaiveDate, String>> {
+        self.item(0..self.value.len(), |text| {
+            let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|e| e.to_string())?;
+
+            let lengths: Vec<_> = text.split('-').map(str::len).collect();
+            if lengths != [4, 2, 2] {
+                return Err("invalid length".into());
+            }
+
+            Ok(date)
+        })
+    }
+
+    /// The value as an absolute URL.
+    pub fn url(&self) -> Item<'a, Result<Url, url::ParseError>> {
+        self.item(0..self.value.len(), Url::parse)
+    }
+
+    /// The comma-separated items in the value, including empty ones.
+    pub fn list(&self) -> Vec<Item<'a, &'a str>> {
+        self.split()
+            .into_iter()
+            .map(|r| self.item(r, |t| t))
+            .collect()
+    }
+
+    /// The comma-separated non-negative integers in the value.
+    pub fn uints(&self) -> Vec<Item<'a, Result<u64, ParseIntError>>> {
+        self.split()
+            .into_iter()
+            .map(|r| self.item(r, str::parse))
+            .collect()
+    }
+
+    /// The items in an author header, including empty ones. Commas inside
+    /// quotes, parentheses, and angle brackets don't separate authors.
+    pub fn author_list(&self) -> Vec<Item<'a, &'a str>> {
+        author::split(self.value)
+            .into_iter()
+            .map(|r| self.item(r, |t| t))
+            .collect()
+    }
+
+    /// The authors in the value, like `Random J. User (@username)`, split like
+    /// [`Field::author_list`]. Empty items are left out.
+    ///
+    /// Ranges in [`InvalidAuthor`] are relative to the item's text.
+    pub fn authors(&self) -> Vec<Item<'a, Result<ParsedAuthor<'a>, InvalidAuthor>>> {
+        author::split(self.value)
+            .into_iter()
+            .map(|r| self.item(r, author::parse))
+            .filter(|i| !i.text.is_empty())
+            .collect()
+    }
+}
+
+/// A value parsed from a preamble field, along with where it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Item<'a, T> {
+    value: T,
+    text: &'a str,
+    range: (usize, usize),
+    padded: (usize, usize),
+}
+
+impl<'a, T> Item<'a, T> {
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// The text the value was parsed from, without whitespace around it.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Where `text` is in the field's source, in characters.
+    pub fn range(&self) -> (usize, usize) {
+        self.range
+    }
+
+    /// Like [`Item::range`], but including the whitespace around the item.
+    pub fn padded_range(&self) -> (usize, usize) {
+        self.padded
+    }
+
+    /// Converts a byte range in `text` into a range of characters in the
+    /// field's source.
+    pub fn sub_range(&self, bytes: Range<usize>) -> (usize, usize) {
+        (
+            self.range.0 + self.text[..bytes.start].chars().count(),
+            self.range.0 + self.text[..bytes.end].chars().count(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -303,4 +463,60 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn uints() {
+        let input = "requires: 1,  x ,3";
+        let preamble = Preamble::parse(None, input).unwrap();
+        let items = preamble.by_name("requires").unwrap().uints();
+
+        let actual: Vec<_> = items
+            .iter()
+            .map(|i| (i.value().as_ref().ok().copied(), i.text(), i.range()))
+            .collect();
+
+        assert_eq!(
+            actual,
+            [
+                (Some(1), "1", (10, 11)),
+                (None, "x", (14, 15)),
+                (Some(3), "3", (17, 18)),
+            ]
+        );
+        assert_eq!(items[1].padded_range(), (12, 16));
+    }
+
+    #[test]
+    fn date() {
+        let input = "created: 2022-1-31\nupdated:  2022-01-31";
+        let preamble = Preamble::parse(None, input).unwrap();
+
+        let created = preamble.by_name("created").unwrap().date();
+        assert_eq!(created.value(), &Err("invalid length".to_owned()));
+        assert_eq!(created.range(), (9, 18));
+
+        let updated = preamble.by_name("updated").unwrap().date();
+        assert_eq!(
+            updated.value(),
+            &Ok(NaiveDate::from_ymd_opt(2022, 1, 31).unwrap())
+        );
+        assert_eq!(updated.range(), (10, 20));
+    }
+
+    #[test]
+    fn authors() {
+        let input = "author: Stéphane (@s), \"A, B\" <a@b.c>,, C(@c)";
+        let preamble = Preamble::parse(None, input).unwrap();
+        let authors = preamble.by_name("author").unwrap().authors();
+
+        let texts: Vec<_> = authors.iter().map(Item::text).collect();
+        assert_eq!(texts, ["Stéphane (@s)", "\"A, B\" <a@b.c>", "C(@c)"]);
+
+        assert_eq!(authors[0].value().as_ref().unwrap().github, Some("s"));
+        assert_eq!(authors[0].range(), (8, 21));
+
+        let error = authors[2].value().as_ref().unwrap_err();
+        assert_eq!(error.label, "missing space");
+        assert_eq!(authors[2].sub_range(error.range.clone()), (41, 42));
+    }
 }
@@ -0,0 +1,323 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Parsing email addresses (the `addr-spec` of RFC 5322, with the UTF-8
+//! extensions of RFC 6531.)
+//!
+//! Obsolete syntax, comments, and folding whitespace aren't accepted, since
+//! nobody writes them in a preamble on purpose.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::Range;
+
+/// The longest local part, in bytes (RFC 5321.)
+const MAX_LOCAL: usize = 64;
+
+/// The longest address, in bytes (RFC 5321, minus the angle brackets.)
+const MAX_ADDRESS: usize = 254;
+
+/// The longest domain label, in bytes.
+const MAX_LABEL: usize = 63;
+
+/// Providers of throwaway inboxes, which can't be used to reach anyone later.
+const DISPOSABLE: &[&str] = &[
+    "10minutemail.com",
+    "burnermail.io",
+    "dispostable.com",
+    "emailondeck.com",
+    "fakeinbox.com",
+    "getnada.com",
+    "guerrillamail.com",
+    "mailinator.com",
+    "maildrop.cc",
+    "mailnesia.com",
+    "mintemail.com",
+    "mohmal.com",
+    "moakt.com",
+    "sharklasers.com",
+    "tempail.com",
+    "temp-mail.org",
+    "tempmail.com",
+    "throwawaymail.com",
+    "trashmail.com",
+    "yopmail.com",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Address<'a> {
+    pub local: &'a str,
+    pub domain: &'a str,
+}
+
+impl<'a> Address<'a> {
+    /// Whether the domain belongs to a provider of disposable addresses.
+    pub fn is_disposable(&self) -> bool {
+        let domain = self.domain.to_lowercase();
+        DISPOSABLE
+            .iter()
+            .any(|d| domain == *d || domain.ends_with(&format!(".{}", d)))
+    }
+}
+
+/// Why an address couldn't be parsed, and where (as a byte range.)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Invalid {
+    pub label: &'static str,
+    pub range: Range<usize>,
+}
+
+fn invalid<T>(label: &'static str, range: Range<usize>) -> Result<T, Invalid> {
+    Err(Invalid { label, range })
+}
+
+/// Parses `text` as a single email address, like `user@example.com`.
+pub(crate) fn parse(text: &str) -> Result<Address<'_>, Invalid> {
+    if text.is_empty() {
+        return invalid("missing email address", 0..0);
+    }
+
+    let at = local_part(text)?;
+
+    if text.len() > MAX_ADDRESS {
+        return invalid("address is longer than 254 bytes", 0..text.len());
+    }
+
+    let local = &text[..at];
+    let domain = &text[at + 1..];
+
+    if local.len() > MAX_LOCAL {
+        return invalid("local part is longer than 64 bytes", 0..at);
+    }
+
+    domain_part(domain).map_err(|e| Invalid {
+        range: e.range.start + at + 1..e.range.end + at + 1,
+        ..e
+    })?;
+
+    Ok(Address { local, domain })
+}
+
+/// Allowed in a dot-atom (`atext`), including any non-ASCII character.
+fn is_atext(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~".contains(c) || !c.is_ascii()
+}
+
+/// Checks the local part of `text`, returning the position of the `@` after
+/// it.
+fn local_part(text: &str) -> Result<usize, Invalid> {
+    if let Some(rest) = text.strip_prefix('"') {
+        let mut escaped = false;
+
+        for (index, c) in rest.char_indices() {
+            let index = index + 1;
+
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    return match text[index + 1..].chars().next() {
+                        Some('@') if index == 1 => invalid("missing local part", 0..2),
+                        Some('@') => Ok(index + 1),
+                        Some(c) => invalid(
+                            "expected `@` after quoted local part",
+                            index + 1..index + 1 + c.len_utf8(),
+                        ),
+                        None => invalid("missing `@`", 0..text.len()),
+                    };
+                }
+                c if c.is_control() => {
+                    return invalid(
+                        "invalid character in local part",
+                        index..index + c.len_utf8(),
+                    );
+                }
+                _ => (),
+            }
+        }
+
+        return invalid("unterminated quoted local part", 0..text.len());
+    }
+
+    let at = match text.find('@') {
+        Some(0) => return invalid("missing local part", 0..1),
+        Some(at) => at,
+        None => return invalid("missing `@`", 0..text.len()),
+    };
+
+    let local = &text[..at];
+
+    for (index, c) in local.char_indices() {
+        if c == '.' {
+            let dots = local[index..].chars().take_while(|c| *c == '.').count();
+            if index == 0 || index + 1 == local.len() || dots > 1 {
+                return invalid("misplaced `.` in local part", index..index + dots.max(1));
+            }
+            continue;
+        }
+
+        if !is_atext(c) {
+            let label = if c.is_whitespace() {
+                "unexpected whitespace in local part"
+            } else {
+                "invalid character in local part"
+            };
+            return invalid(label, index..index + c.len_utf8());
+        }
+    }
+
+    Ok(at)
+}
+
+fn domain_part(domain: &str) -> Result<(), Invalid> {
+    if domain.is_empty() {
+        return invalid("missing domain", 0..0);
+    }
+
+    if let Some(literal) = domain.strip_prefix('[') {
+        let literal = match literal.strip_suffix(']') {
+            Some(l) => l,
+            None => return invalid("unterminated address literal", 0..domain.len()),
+        };
+
+        let valid = match literal.strip_prefix("IPv6:") {
+            Some(v6) => v6.parse::<Ipv6Addr>().is_ok(),
+            None => literal.parse::<Ipv4Addr>().is_ok(),
+        };
+
+        if valid {
+            return Ok(());
+        }
+
+        return invalid("invalid address literal", 0..domain.len());
+    }
+
+    let mut start = 0;
+    let mut labels = 0;
+
+    for label in domain.split('.') {
+        let range = start..start + label.len();
+        start += label.len() + 1;
+        labels += 1;
+
+        if label.is_empty() {
+            // Point at the `.` next to the empty label, which comes before it
+            // at the end of the domain.
+            let dot = range.start.min(domain.len() - 1);
+            return invalid("empty label in domain", dot..dot + 1);
+        }
+
+        if let Some((index, c)) = label
+            .char_indices()
+            .find(|(_, c)| !c.is_alphanumeric() && *c != '-')
+        {
+            let at = range.start + index;
+            let label = if c == '@' {
+                "more than one `@`"
+            } else {
+                "invalid character in domain"
+            };
+            return invalid(label, at..at + c.len_utf8());
+        }
+
+        if label.starts_with('-') || label.ends_with('-') {
+            return invalid("domain labels can't start or end with `-`", range);
+        }
+
+        if label.len() > MAX_LABEL {
+            return invalid("domain label is longer than 63 bytes", range);
+        }
+    }
+
+    let tld = domain.rsplit('.').next().unwrap();
+    let tld_range = domain.len() - tld.len()..domain.len();
+
+    if labels < 2 {
+        return invalid("missing TLD", domain.len()..domain.len());
+    }
+
+    if tld.chars().all(|c| c.is_ascii_digit()) {
+        return invalid("TLD can't be numeric", tld_range);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn label(text: &str) -> Result<(), (&'static str, &str)> {
+        parse(text)
+            .map(|_| ())
+            .map_err(|e| (e.label, &text[e.range]))
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(label("user@example.com"), Ok(()));
+        assert_eq!(label("first.last+tag@sub.example.co.uk"), Ok(()));
+        assert_eq!(label("\"john doe\"@example.com"), Ok(()));
+        assert_eq!(label("\"a@b\"@example.com"), Ok(()));
+        assert_eq!(label("用户@例子.广告"), Ok(()));
+        assert_eq!(label("user@[192.168.0.1]"), Ok(()));
+        assert_eq!(label("user@[IPv6:2001:db8::1]"), Ok(()));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            label("user.example.com"),
+            Err(("missing `@`", "user.example.com"))
+        );
+        assert_eq!(label("@example.com"), Err(("missing local part", "@")));
+        assert_eq!(label("user@"), Err(("missing domain", "")));
+        assert_eq!(label("user@example"), Err(("missing TLD", "")));
+        assert_eq!(
+            label("us(er@example.com"),
+            Err(("invalid character in local part", "("))
+        );
+        assert_eq!(
+            label("us..er@example.com"),
+            Err(("misplaced `.` in local part", ".."))
+        );
+        assert_eq!(
+            label(".user@example.com"),
+            Err(("misplaced `.` in local part", "."))
+        );
+        assert_eq!(label("a@b@example.com"), Err(("more than one `@`", "@")));
+        assert_eq!(
+            label("user@exa_mple.com"),
+            Err(("invalid character in domain", "_"))
+        );
+        assert_eq!(
+            label("user@example..com"),
+            Err(("empty label in domain", "."))
+        );
+        assert_eq!(
+            label("user@example.com."),
+            Err(("empty label in domain", "."))
+        );
+        assert_eq!(
+            label("user@-example.com"),
+            Err(("domain labels can't start or end with `-`", "-example"))
+        );
+        assert_eq!(
+            label("user@example.123"),
+            Err(("TLD can't be numeric", "123"))
+        );
+        assert_eq!(
+            label("\"user@example.com"),
+            Err(("unterminated quoted local part", "\"user@example.com"))
+        );
+    }
+
+    #[test]
+    fn disposable() {
+        assert!(parse("me@mailinator.com").unwrap().is_disposable());
+        assert!(parse("me@eu.Mailinator.com").unwrap().is_disposable());
+        assert!(!parse("me@example.com").unwrap().is_disposable());
+    }
+}
@@ -0,0 +1,465 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Parsing author headers, like `Random J. User (@username), Jane Doe`.
+
+use std::ops::Range;
+
+use super::address;
+
+/// One author listed in an author header, like `Random J. User (@username)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsedAuthor<'a> {
+    /// The author's name, without any quotes around it.
+    pub name: &'a str,
+
+    /// The GitHub username, without the `@`.
+    pub github: Option<&'a str>,
+
+    /// The email address, without the angle brackets.
+    pub email: Option<&'a str>,
+}
+
+/// Why an author in an author header couldn't be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidAuthor {
+    pub title: &'static str,
+    pub label: &'static str,
+
+    /// Where the problem is in the author's text, in bytes.
+    pub range: Range<usize>,
+
+    /// The author written the way it was probably meant to be, if there's a
+    /// likely fix.
+    pub suggestion: Option<String>,
+}
+
+pub(crate) const FORMAT: &str = "authors in the preamble must match the expected format";
+pub(crate) const USERNAME: &str = "GitHub usernames in the preamble must be valid";
+pub(crate) const EMAIL: &str = "author email addresses must be valid";
+
+/// Splits an author header into its entries, at commas that aren't inside a
+/// quoted string, angle brackets, or parentheses.
+pub(crate) fn split(value: &str) -> Vec<Range<usize>> {
+    let mut items = vec![];
+    let mut start = 0;
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+
+    for (index, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            _ if quoted => (),
+            '<' | '(' => depth += 1,
+            '>' | ')' if depth > 0 => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(start..index);
+                start = index + 1;
+            }
+            _ => (),
+        }
+    }
+
+    items.push(start..value.len());
+    items
+}
+
+/// Whether `name` follows GitHub's rules for usernames: up to 39 letters,
+/// digits, and hyphens, without leading, trailing, or consecutive hyphens.
+pub(crate) fn is_github_username(name: &str) -> bool {
+    username_problem(name).is_none()
+}
+
+fn fail<T>(
+    title: &'static str,
+    label: &'static str,
+    range: Range<usize>,
+    suggestion: Option<String>,
+) -> Result<T, InvalidAuthor> {
+    Err(InvalidAuthor {
+        title,
+        label,
+        range,
+        suggestion,
+    })
+}
+
+const EXAMPLE_NAME: &str = "Random J. User";
+
+/// The suggestion for authors that are too garbled to fix.
+fn example() -> Option<String> {
+    Some(format!("{} (@username)", EXAMPLE_NAME))
+}
+
+/// `text` with `insert` added at byte `at`, and `remove` bytes taken out
+/// after it.
+fn fix(text: &str, at: usize, remove: usize, insert: &str) -> Option<String> {
+    Some(format!("{}{}{}", &text[..at], insert, &text[at + remove..]))
+}
+
+/// Parses one author, like `Random J. User (@username)`, without any
+/// whitespace around it.
+///
+/// Each author is a name, followed by a GitHub username in parentheses, an
+/// email address in angle brackets, or both (in either order.) Names
+/// containing commas or other special characters can be quoted.
+pub fn parse(text: &str) -> Result<ParsedAuthor<'_>, InvalidAuthor> {
+    let (name, mut pos) = name(text)?;
+
+    if name.trim().is_empty() {
+        return fail(
+            FORMAT,
+            "missing name",
+            0..pos.max(1),
+            Some(format!("{} {}", EXAMPLE_NAME, text.trim_start())),
+        );
+    }
+
+    let mut github = None;
+    let mut email = None;
+
+    while pos < text.len() {
+        let spaces = text[pos..].len() - text[pos..].trim_start().len();
+
+        if spaces == 0 {
+            return fail(
+                FORMAT,
+                "missing space",
+                pos..pos + 1,
+                fix(text, pos, 0, " "),
+            );
+        }
+
+        if spaces > 1 || !text[pos..].starts_with(' ') {
+            return fail(
+                FORMAT,
+                "extra whitespace",
+                pos..pos + spaces,
+                fix(text, pos, spaces, " "),
+            );
+        }
+
+        pos += 1;
+        let rest = &text[pos..];
+
+        // The next whitespace-separated word.
+        let word = &rest[..rest.find(char::is_whitespace).unwrap_or(rest.len())];
+
+        if rest.starts_with('(') {
+            let close = match rest.find(')') {
+                Some(c) => c,
+                None => {
+                    return fail(
+                        FORMAT,
+                        "missing `)`",
+                        pos..text.len(),
+                        fix(text, text.len(), 0, ")"),
+                    )
+                }
+            };
+
+            let inner = &rest[1..close];
+            let user = match inner.strip_prefix('@') {
+                Some(u) => u,
+                None => {
+                    return fail(
+                        FORMAT,
+                        "missing `@` before GitHub username",
+                        pos + 1..pos + 1 + inner.len(),
+                        fix(text, pos + 1, 0, "@"),
+                    )
+                }
+            };
+
+            let range = pos + 2..pos + 2 + user.len();
+
+            if let Some(label) = username_problem(user) {
+                return fail(USERNAME, label, range, None);
+            }
+
+            if github.is_some() {
+                return fail(FORMAT, "more than one GitHub username", range, None);
+            }
+
+            github = Some(user);
+            pos += close + 1;
+        } else if rest.starts_with('<') {
+            let close = match rest.find('>') {
+                Some(c) => c,
+                None => {
+                    return fail(
+                        FORMAT,
+                        "missing `>`",
+                        pos..text.len(),
+                        fix(text, text.len(), 0, ">"),
+                    )
+                }
+            };
+
+            let address = &rest[1..close];
+
+            if let Err(e) = address::parse(address) {
+                let at = pos + 1;
+                return fail(EMAIL, e.label, e.range.start + at..e.range.end + at, None);
+            }
+
+            if email.is_some() {
+                return fail(
+                    FORMAT,
+                    "more than one email address",
+                    pos + 1..pos + 1 + address.len(),
+                    None,
+                );
+            }
+
+            email = Some(address);
+            pos += close + 1;
+        } else if word.starts_with('@') {
+            return fail(
+                FORMAT,
+                "missing parentheses around GitHub username",
+                pos..pos + word.len(),
+                Some(format!(
+                    "{}({}){}",
+                    &text[..pos],
+                    word,
+                    &text[pos + word.len()..]
+                )),
+            );
+        } else if word.contains('@') {
+            return fail(
+                FORMAT,
+                "missing angle brackets around email address",
+                pos..pos + word.len(),
+                Some(format!(
+                    "{}<{}>{}",
+                    &text[..pos],
+                    word,
+                    &text[pos + word.len()..]
+                )),
+            );
+        } else {
+            return fail(
+                FORMAT,
+                "unexpected text after author",
+                pos..text.len(),
+                example(),
+            );
+        }
+    }
+
+    Ok(ParsedAuthor {
+        name,
+        github,
+        email,
+    })
+}
+
+/// Parses the name at the start of `text`, returning it and the byte position
+/// right after it.
+fn name(text: &str) -> Result<(&str, usize), InvalidAuthor> {
+    if let Some(rest) = text.strip_prefix('"') {
+        let mut escaped = false;
+
+        for (index, c) in rest.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => return Ok((&rest[..index], index + 2)),
+                _ => (),
+            }
+        }
+
+        return fail(FORMAT, "unterminated quoted name", 0..text.len(), example());
+    }
+
+    let mut word_start = 0;
+    let mut previous = ' ';
+
+    for (index, c) in text.char_indices() {
+        if previous.is_whitespace() && !c.is_whitespace() {
+            word_start = index;
+        }
+        previous = c;
+
+        match c {
+            '(' | '<' => return Ok((text[..index].trim_end(), trim_end_index(text, index))),
+            '@' => {
+                return Ok((
+                    text[..word_start].trim_end(),
+                    trim_end_index(text, word_start),
+                ))
+            }
+            ')' | '>' | '"' | ',' => {
+                return fail(
+                    FORMAT,
+                    "unexpected character in name",
+                    index..index + 1,
+                    example(),
+                )
+            }
+            _ => (),
+        }
+    }
+
+    Ok((text, text.len()))
+}
+
+/// The position of the whitespace before byte `end` in `text`, if there is
+/// any.
+fn trim_end_index(text: &str, end: usize) -> usize {
+    text[..end].trim_end().len()
+}
+
+/// Why `name` isn't a valid GitHub username, if it isn't.
+fn username_problem(name: &str) -> Option<&'static str> {
+    if name.is_empty() {
+        Some("missing GitHub username")
+    } else if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        Some("GitHub usernames only have letters, digits, and hyphens")
+    } else if name.len() > 39 {
+        Some("GitHub usernames are at most 39 characters")
+    } else if name.starts_with('-') || name.ends_with('-') {
+        Some("GitHub usernames can't start or end with `-`")
+    } else if name.contains("--") {
+        Some("GitHub usernames can't contain `--`")
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(text: &str) -> (&'static str, &str, Option<String>) {
+        let error = parse(text).unwrap_err();
+        (error.label, &text[error.range], error.suggestion)
+    }
+
+    #[test]
+    fn split_entries() {
+        let value = r#"A (@a), "B, C" <b@example.com>, D <"d,e"@example.com>"#;
+        let items: Vec<_> = split(value).into_iter().map(|r| value[r].trim()).collect();
+        assert_eq!(
+            items,
+            [
+                "A (@a)",
+                r#""B, C" <b@example.com>"#,
+                r#"D <"d,e"@example.com>"#
+            ]
+        );
+    }
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            parse("Stéphane Barroso (@sudoweezy)"),
+            Ok(ParsedAuthor {
+                name: "Stéphane Barroso",
+                github: Some("sudoweezy"),
+                email: None,
+            })
+        );
+        assert_eq!(
+            parse(r#""User, J." <j@example.com> (@j-u)"#),
+            Ok(ParsedAuthor {
+                name: "User, J.",
+                github: Some("j-u"),
+                email: Some("j@example.com"),
+            })
+        );
+        assert_eq!(
+            parse("Name"),
+            Ok(ParsedAuthor {
+                name: "Name",
+                github: None,
+                email: None,
+            })
+        );
+    }
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(
+            problem("Stéphane Barroso(@sudoweezy)"),
+            (
+                "missing space",
+                "(",
+                Some("Stéphane Barroso (@sudoweezy)".into())
+            )
+        );
+        assert_eq!(
+            problem("Random User @user"),
+            (
+                "missing parentheses around GitHub username",
+                "@user",
+                Some("Random User (@user)".into())
+            )
+        );
+        assert_eq!(
+            problem("Random User (user)"),
+            (
+                "missing `@` before GitHub username",
+                "user",
+                Some("Random User (@user)".into())
+            )
+        );
+        assert_eq!(
+            problem("Random User user@example.com"),
+            (
+                "missing angle brackets around email address",
+                "user@example.com",
+                Some("Random User <user@example.com>".into())
+            )
+        );
+        assert_eq!(
+            problem("Random User (@user"),
+            ("missing `)`", "(@user", Some("Random User (@user)".into()))
+        );
+        assert_eq!(
+            problem("Random User  (@user)"),
+            ("extra whitespace", "  ", Some("Random User (@user)".into()))
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            problem("A (@-user)"),
+            (
+                "GitHub usernames can't start or end with `-`",
+                "-user",
+                None
+            )
+        );
+        assert_eq!(
+            problem("A (@a--b)"),
+            ("GitHub usernames can't contain `--`", "a--b", None)
+        );
+        assert_eq!(
+            problem(&format!("A (@{})", "a".repeat(40))).0,
+            "GitHub usernames are at most 39 characters"
+        );
+        assert_eq!(
+            problem("A (@a) (@b)"),
+            ("more than one GitHub username", "b", None)
+        );
+        assert_eq!(problem("A <a@example>"), ("missing TLD", "", None));
+        assert_eq!(
+            problem("A (@a) extra"),
+            (
+                "unexpected text after author",
+                "extra",
+                Some("Random J. User (@username)".into())
+            )
+        );
+        assert_eq!(problem("(@a)").0, "missing name");
+    }
+}
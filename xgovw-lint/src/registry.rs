@@ -0,0 +1,448 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Every lint type in this crate, constructible by name from JSON arguments.
+//!
+//! Arguments are deserialized into the lint's fields, so tuple lints like
+//! `preamble::Date` take a bare value (`"created"`), struct lints like
+//! `preamble::Length` take an object (`{"name": "title", "max": 44}`), and
+//! lints without configuration take `null`.
+
+use crate::lints::{markdown, preamble, Lint, LintExt};
+
+use serde::de::DeserializeOwned;
+
+use snafu::{ResultExt, Snafu};
+
+#[derive(Debug, Snafu)]
+#[non_exhaustive]
+pub enum Error {
+    #[snafu(display("unknown lint kind `{}`{}", kind, did_you_mean(suggestion)))]
+    UnknownKind {
+        kind: String,
+        suggestion: Option<&'static str>,
+    },
+    #[snafu(display("invalid arguments for lint kind `{}`: {}", kind, source))]
+    InvalidArguments {
+        kind: &'static str,
+        source: serde_json::Error,
+    },
+}
+
+/// Formats an optional suggestion as a sentence fragment, suitable for
+/// appending to an error message.
+pub fn did_you_mean<S>(suggestion: &Option<S>) -> String
+where
+    S: AsRef<str>,
+{
+    match suggestion {
+        Some(s) => format!(" (did you mean `{}`?)", s.as_ref()),
+        None => String::new(),
+    }
+}
+
+/// Finds the candidate closest to `input`, if any is close enough to be a
+/// plausible typo.
+pub fn suggest<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let input = input.to_lowercase();
+
+    candidates
+        .into_iter()
+        .map(|c| (strsim::levenshtein(&input, &c.to_lowercase()), c))
+        .filter(|(distance, c)| *distance <= 3.max(c.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+type Build = fn(serde_json::Value) -> Result<Box<dyn Lint>, serde_json::Error>;
+
+fn build<L>(args: serde_json::Value) -> Result<Box<dyn Lint>, serde_json::Error>
+where
+    L: 'static + Lint + DeserializeOwned,
+{
+    serde_json::from_value::<L>(args).map(LintExt::boxed)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Kind {
+    name: &'static str,
+    build: Build,
+}
+
+impl Kind {
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn build(&self, args: serde_json::Value) -> Result<Box<dyn Lint>, Error> {
+        (self.build)(args).context(InvalidArgumentsSnafu { kind: self.name })
+    }
+}
+
+const KINDS: &[Kind] = &[
+    //
+    // Markdown
+    //
+    Kind {
+        name: "markdown::BlankLines",
+        build: build::<markdown::BlankLines>,
+    },
+    Kind {
+        name: "markdown::Boilerplate",
+        build: build::<markdown::Boilerplate<String>>,
+    },
+    Kind {
+        name: "markdown::Confusables",
+        build: build::<markdown::Confusables<String>>,
+    },
+    Kind {
+        name: "markdown::ExternalLinks",
+        build: build::<markdown::ExternalLinks<String>>,
+    },
+    Kind {
+        name: "markdown::FinalNewline",
+        build: build::<markdown::FinalNewline>,
+    },
+    Kind {
+        name: "markdown::Headings",
+        build: build::<markdown::Headings<String>>,
+    },
+    Kind {
+        name: "markdown::Html",
+        build: build::<markdown::Html<String>>,
+    },
+    Kind {
+        name: "markdown::Images",
+        build: build::<markdown::Images<String>>,
+    },
+    Kind {
+        name: "markdown::LineEndings",
+        build: build::<markdown::LineEndings>,
+    },
+    Kind {
+        name: "markdown::LineLength",
+        build: build::<markdown::LineLength>,
+    },
+    Kind {
+        name: "markdown::LinkFirst",
+        build: build::<markdown::LinkFirst<String>>,
+    },
+    Kind {
+        name: "markdown::LinkStatus",
+        build: build::<markdown::LinkStatus<String>>,
+    },
+    Kind {
+        name: "markdown::LinkTargets",
+        build: build::<markdown::LinkTargets>,
+    },
+    Kind {
+        name: "markdown::LinkText",
+        build: build::<markdown::LinkText>,
+    },
+    Kind {
+        name: "markdown::Privacy",
+        build: build::<markdown::Privacy<String>>,
+    },
+    Kind {
+        name: "markdown::Regex",
+        build: build::<markdown::Regex<String>>,
+    },
+    Kind {
+        name: "markdown::RelativeLinks",
+        build: build::<markdown::RelativeLinks>,
+    },
+    Kind {
+        name: "markdown::Secrets",
+        build: build::<markdown::Secrets>,
+    },
+    Kind {
+        name: "markdown::SectionLength",
+        build: build::<markdown::SectionLength<String>>,
+    },
+    Kind {
+        name: "markdown::SectionOrder",
+        build: build::<markdown::SectionOrder<String>>,
+    },
+    Kind {
+        name: "markdown::SectionRequired",
+        build: build::<markdown::SectionRequired<String>>,
+    },
+    Kind {
+        name: "markdown::SocialProfiles",
+        build: build::<markdown::SocialProfiles<String>>,
+    },
+    Kind {
+        name: "markdown::Tables",
+        build: build::<markdown::Tables<String>>,
+    },
+    Kind {
+        name: "markdown::Tabs",
+        build: build::<markdown::Tabs>,
+    },
+    Kind {
+        name: "markdown::TrailingWhitespace",
+        build: build::<markdown::TrailingWhitespace>,
+    },
+    //
+    // Preamble
+    //
+    Kind {
+        name: "preamble::Allowed",
+        build: build::<preamble::Allowed<String>>,
+    },
+    Kind {
+        name: "preamble::Author",
+        build: build::<preamble::Author<String>>,
+    },
+    Kind {
+        name: "preamble::Date",
+        build: build::<preamble::Date<String>>,
+    },
+    Kind {
+        name: "preamble::Email",
+        build: build::<preamble::Email<String>>,
+    },
+    Kind {
+        name: "preamble::FileName",
+        build: build::<preamble::FileName<String>>,
+    },
+    Kind {
+        name: "preamble::Length",
+        build: build::<preamble::Length<String>>,
+    },
+    Kind {
+        name: "preamble::List",
+        build: build::<preamble::List<String>>,
+    },
+    Kind {
+        name: "preamble::NoDuplicates",
+        build: build::<preamble::NoDuplicates>,
+    },
+    Kind {
+        name: "preamble::OneOf",
+        build: build::<preamble::OneOf<String>>,
+    },
+    Kind {
+        name: "preamble::Order",
+        build: build::<preamble::Order<String>>,
+    },
+    Kind {
+        name: "preamble::Regex",
+        build: build::<preamble::Regex<String>>,
+    },
+    Kind {
+        name: "preamble::RequireReferenced",
+        build: build::<preamble::RequireReferenced<String>>,
+    },
+    Kind {
+        name: "preamble::Required",
+        build: build::<preamble::Required<String>>,
+    },
+    Kind {
+        name: "preamble::RequiredIfEq",
+        build: build::<preamble::RequiredIfEq<String>>,
+    },
+    Kind {
+        name: "preamble::RequiresStatus",
+        build: build::<preamble::RequiresStatus<String>>,
+    },
+    Kind {
+        name: "preamble::Trim",
+        build: build::<preamble::Trim>,
+    },
+    Kind {
+        name: "preamble::Uint",
+        build: build::<preamble::Uint<String>>,
+    },
+    Kind {
+        name: "preamble::UintList",
+        build: build::<preamble::UintList<String>>,
+    },
+    Kind {
+        name: "preamble::Url",
+        build: build::<preamble::Url<String>>,
+    },
+];
+
+pub fn kinds() -> impl Iterator<Item = &'static Kind> {
+    KINDS.iter()
+}
+
+pub fn find(kind: &str) -> Result<&'static Kind, Error> {
+    match kinds().find(|k| k.name == kind) {
+        Some(k) => Ok(k),
+        None => UnknownKindSnafu {
+            kind,
+            suggestion: suggest(kind, kinds().map(Kind::name)),
+        }
+        .fail(),
+    }
+}
+
+/// Constructs a lint of the given kind (eg. `preamble::Date`) from its
+/// JSON-encoded arguments.
+pub fn build_lint(kind: &str, args: serde_json::Value) -> Result<Box<dyn Lint>, Error> {
+    find(kind)?.build(args)
+}
+
+/// Arguments for building one lint of every kind, for tests.
+#[cfg(test)]
+pub(crate) fn example_args() -> Vec<(&'static str, serde_json::Value)> {
+    use serde_json::json;
+
+    vec![
+        ("markdown::BlankLines", json!(null)),
+        (
+            "markdown::Boilerplate",
+            json!({
+                "template": "---\nauthor: Random J. User (@username)\n---\n\n## Introduction\n\nWrite about yourself.\n",
+                "fields": ["author"],
+            }),
+        ),
+        (
+            "markdown::Confusables",
+            json!({"names": ["author"], "known": ["algorandfoundation"]}),
+        ),
+        (
+            "markdown::ExternalLinks",
+            json!({"schemes": ["https"], "deny": ["bit.ly"]}),
+        ),
+        ("markdown::FinalNewline", json!(null)),
+        (
+            "markdown::Headings",
+            json!({"sections": [{"name": "Introduction", "subsections": ["Background"]}]}),
+        ),
+        ("markdown::Html", json!({"tags": ["br"], "attributes": []})),
+        (
+            "markdown::Images",
+            json!({"formats": ["png", "jpeg"], "max_bytes": 1000000}),
+        ),
+        ("markdown::LineEndings", json!(null)),
+        ("markdown::LineLength", json!({"max": 100})),
+        ("markdown::LinkFirst", json!("requires")),
+        (
+            "markdown::LinkStatus",
+            json!({"status": "status", "flow": [["Draft"], ["Final"]]}),
+        ),
+        ("markdown::LinkTargets", json!({"depth": 1})),
+        ("markdown::LinkText", json!(null)),
+        (
+            "markdown::Privacy",
+            json!({"email": "email", "sections": ["Social Profiles"]}),
+        ),
+        (
+            "markdown::Regex",
+            json!({"mode": "excludes", "pattern": "TODO", "message": "no TODOs"}),
+        ),
+        ("markdown::RelativeLinks", json!(null)),
+        ("markdown::Secrets", json!(null)),
+        (
+            "markdown::SectionLength",
+            json!({"section": "Introduction", "min_words": 10}),
+        ),
+        ("markdown::SectionOrder", json!(["Introduction"])),
+        ("markdown::SectionRequired", json!(["Introduction"])),
+        (
+            "markdown::SocialProfiles",
+            json!({"section": "Social Profiles", "author": "author"}),
+        ),
+        (
+            "markdown::Tables",
+            json!({"columns": [{"section": "Project Affiliations", "names": ["Project", "Role"]}]}),
+        ),
+        ("markdown::Tabs", json!(null)),
+        ("markdown::TrailingWhitespace", json!(null)),
+        ("preamble::Allowed", json!(["id", "author"])),
+        ("preamble::Author", json!("author")),
+        ("preamble::Date", json!("created")),
+        (
+            "preamble::Email",
+            json!({"name": "email", "reject_disposable": true}),
+        ),
+        (
+            "preamble::FileName",
+            json!({"name": "id", "prefix": "xgov-", "suffix": ".md"}),
+        ),
+        ("preamble::Length", json!({"name": "title", "max": 44})),
+        ("preamble::List", json!("author")),
+        ("preamble::NoDuplicates", json!(null)),
+        (
+            "preamble::OneOf",
+            json!({"name": "status", "values": ["Draft", "Final"]}),
+        ),
+        ("preamble::Order", json!(["id", "author"])),
+        (
+            "preamble::Regex",
+            json!({"name": "title", "mode": "excludes", "pattern": "TODO", "message": "no TODOs"}),
+        ),
+        (
+            "preamble::RequireReferenced",
+            json!({"name": "status", "requires": "requires"}),
+        ),
+        ("preamble::Required", json!(["id", "author"])),
+        (
+            "preamble::RequiredIfEq",
+            json!({"when": "status", "equals": "Final", "then": "address"}),
+        ),
+        (
+            "preamble::RequiresStatus",
+            json!({"requires": "requires", "status": "status", "flow": [["Draft"], ["Final"]]}),
+        ),
+        ("preamble::Trim", json!(null)),
+        ("preamble::Uint", json!("id")),
+        ("preamble::UintList", json!("requires")),
+        ("preamble::Url", json!("discussions-to")),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn build_every_kind() {
+        let args = example_args();
+
+        assert_eq!(args.len(), kinds().count());
+
+        for (kind, args) in args {
+            if let Err(e) = build_lint(kind, args) {
+                panic!("couldn't build `{}`: {}", kind, e);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_kind_suggests() {
+        let err = build_lint("preamble::Dates", json!("created")).unwrap_err();
+        assert_matches!(
+            err,
+            Error::UnknownKind {
+                suggestion: Some("preamble::Date"),
+                ..
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        let err = build_lint("preamble::Length", json!("title")).unwrap_err();
+        assert_matches!(
+            err,
+            Error::InvalidArguments {
+                kind: "preamble::Length",
+                ..
+            }
+        );
+    }
+}
@@ -0,0 +1,708 @@
+/*
+ * This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/.
+ */
+
+//! Source positions for markdown nodes.
+//!
+//! comrak only records the line a block starts on, so spans are
+//! reconstructed here: blocks run until the next sibling (minus trailing
+//! blank lines), and inline nodes are found by searching for their text in
+//! order, starting from the beginning of their block.
+
+use comrak::nodes::{Ast, AstNode, NodeValue};
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// A location in a source file. Lines and columns start at one, and columns
+/// count characters (not bytes.)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+}
+
+/// The region of a source file a markdown node was parsed from. The end is
+/// exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Span {
+    start: Position,
+    end: Position,
+}
+
+impl Span {
+    pub fn start(&self) -> Position {
+        self.start
+    }
+
+    pub fn end(&self) -> Position {
+        self.end
+    }
+
+    /// A span from the start of `self` to the end of `other`.
+    pub fn to(&self, other: Span) -> Span {
+        Span {
+            start: self.start,
+            end: other.end,
+        }
+    }
+
+    pub(crate) fn bytes(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+}
+
+/// The complete lines covered by a [`Span`], with the span's range in them,
+/// ready to be used in an `annotate_snippets` `Slice`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanSource<'a> {
+    pub line_start: usize,
+    pub source: &'a str,
+
+    /// Character range of the span within `source`.
+    pub range: (usize, usize),
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Spans {
+    line_starts: Vec<usize>,
+    spans: HashMap<*const Ast, Span>,
+}
+
+impl Spans {
+    pub(crate) fn get(&self, ast: &Ast) -> Option<Span> {
+        self.spans.get(&(ast as *const Ast)).copied()
+    }
+
+    pub(crate) fn position(&self, source: &str, offset: usize) -> Position {
+        let index = self.line_starts.partition_point(|s| *s <= offset) - 1;
+        let line_start = self.line_starts[index];
+
+        Position {
+            offset,
+            line: index + 1,
+            column: source[line_start..offset].chars().count() + 1,
+        }
+    }
+
+    pub(crate) fn span(&self, source: &str, bytes: Range<usize>) -> Span {
+        Span {
+            start: self.position(source, bytes.start),
+            end: self.position(source, bytes.end),
+        }
+    }
+
+    pub(crate) fn source<'a>(&self, source: &'a str, span: Span) -> SpanSource<'a> {
+        let first = self.line_starts[span.start.line - 1];
+        let last = source[span.end.offset..]
+            .find('\n')
+            .map(|i| i + span.end.offset)
+            .unwrap_or(source.len());
+
+        let start = source[first..span.start.offset].chars().count();
+        let len = source[span.bytes()].chars().count();
+
+        SpanSource {
+            line_start: span.start.line,
+            source: &source[first..last],
+            range: (start, start + len),
+        }
+    }
+}
+
+/// Compute the spans of `body` and all its descendants, which were parsed
+/// from `source` starting at `first_line`.
+pub(crate) fn compute<'a>(source: &str, first_line: usize, body: &'a AstNode<'a>) -> Spans {
+    let mut line_starts = vec![0];
+    line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+
+    let mut mapper = Mapper {
+        source,
+        cursor: 0,
+        line_starts: &line_starts,
+        bytes: HashMap::new(),
+    };
+
+    // A document ending on the preamble's closing `---`, without a newline,
+    // has no lines left for the body.
+    let last_line = line_starts.len();
+    if body.first_child().is_some() {
+        mapper.block(body, first_line.min(last_line), last_line);
+    }
+
+    let bytes = mapper.bytes;
+    let mut spans = Spans {
+        spans: HashMap::with_capacity(bytes.len()),
+        line_starts,
+    };
+
+    for (key, range) in bytes {
+        let span = spans.span(source, range);
+        spans.spans.insert(key, span);
+    }
+
+    spans
+}
+
+/// The byte ranges of the cells in a table row, from splitting `line` on
+/// unescaped pipes. Content is trimmed, except in empty cells, which keep
+/// their whitespace.
+pub(crate) fn cells(line: &str) -> Vec<Range<usize>> {
+    let mut pipes = Vec::new();
+    let mut escaped = false;
+
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '|' => pipes.push(index),
+            _ => (),
+        }
+    }
+
+    let mut start = line.len() - line.trim_start().len();
+    let mut end = line.trim_end().len();
+
+    if pipes.first() == Some(&start) {
+        pipes.remove(0);
+        start += 1;
+    }
+
+    if end > start && pipes.last() == Some(&(end - 1)) {
+        pipes.pop();
+        end -= 1;
+    }
+
+    let mut cells = Vec::with_capacity(pipes.len() + 1);
+
+    for pipe in pipes.into_iter().chain(std::iter::once(end)) {
+        let cell = &line[start..pipe];
+        let content = cell.trim();
+
+        if content.is_empty() {
+            cells.push(start..pipe);
+        } else {
+            let offset = start + cell.len() - cell.trim_start().len();
+            cells.push(offset..offset + content.len());
+        }
+
+        start = pipe + 1;
+    }
+
+    cells
+}
+
+/// The length of the block quote markers (`> `) before the content of `node`
+/// on `line`.
+fn container_prefix<'a>(node: &'a AstNode<'a>, line: &str) -> usize {
+    let quotes = node
+        .ancestors()
+        .filter(|n| matches!(n.data.borrow().value, NodeValue::BlockQuote))
+        .count();
+
+    let mut rest = line;
+
+    for _ in 0..quotes {
+        let trimmed = rest.trim_start_matches(' ');
+        rest = match trimmed.strip_prefix('>') {
+            Some(r) => r.strip_prefix([' ', '\t']).unwrap_or(r),
+            None => break,
+        };
+    }
+
+    line.len() - rest.len()
+}
+
+struct Mapper<'s> {
+    source: &'s str,
+    line_starts: &'s [usize],
+    cursor: usize,
+    bytes: HashMap<*const Ast, Range<usize>>,
+}
+
+impl<'s> Mapper<'s> {
+    fn line(&self, line: usize) -> Range<usize> {
+        let start = self.line_starts[line - 1];
+        let end = match self.line_starts.get(line) {
+            Some(next) => next - 1,
+            None => self.source.len(),
+        };
+        start..end
+    }
+
+    fn is_blank(&self, line: usize) -> bool {
+        self.source[self.line(line)].trim().is_empty()
+    }
+
+    fn insert<'a>(&mut self, node: &'a AstNode<'a>, range: Range<usize>) {
+        let data = node.data.borrow();
+        self.bytes.insert(&*data as *const Ast, range);
+    }
+
+    fn get<'a>(&self, node: &'a AstNode<'a>) -> Option<Range<usize>> {
+        let data = node.data.borrow();
+        self.bytes.get(&(&*data as *const Ast)).cloned()
+    }
+
+    fn block<'a>(&mut self, node: &'a AstNode<'a>, start_line: usize, mut end_line: usize) {
+        while end_line > start_line && self.is_blank(end_line) {
+            end_line -= 1;
+        }
+
+        let first = self.line(start_line);
+        let indent = self.source[first.clone()].len() - self.source[first].trim_start().len();
+
+        let mut start = self.line(start_line).start + indent;
+        let end = self.line(end_line).start + self.source[self.line(end_line)].trim_end().len();
+
+        // Table cells (and anything else sharing a line with a sibling) start
+        // where the previous node left off.
+        if self.cursor > start && self.cursor <= end {
+            start = self.cursor;
+        }
+
+        if let NodeValue::TableRow(_) = node.data.borrow().value {
+            self.row(node, start_line);
+            return;
+        }
+
+        let has_inlines = node
+            .first_child()
+            .map(|c| !c.data.borrow().value.block())
+            .unwrap_or(false);
+
+        if has_inlines {
+            self.cursor = self.cursor.max(start);
+            for child in node.children() {
+                self.inline(child, end);
+            }
+
+            if let NodeValue::TableCell = node.data.borrow().value {
+                let first = node.first_child().and_then(|c| self.get(c));
+                let last = node.last_child().and_then(|c| self.get(c));
+                if let (Some(first), Some(last)) = (first, last) {
+                    self.insert(node, first.start..last.end);
+                }
+                return;
+            }
+
+            self.insert(node, start..end.max(start));
+            return;
+        }
+
+        self.insert(node, start..end.max(start));
+
+        let mut child = node.first_child();
+        while let Some(current) = child {
+            let next = current.next_sibling();
+
+            let child_start = match current.data.borrow().start_line as usize {
+                0 => start_line,
+                l => l,
+            };
+
+            let child_end = match next.map(|n| n.data.borrow().start_line as usize) {
+                Some(l) if l > child_start => l - 1,
+                Some(l) if l == child_start => l,
+                _ => end_line,
+            };
+
+            self.block(current, child_start, child_end.max(child_start));
+            child = next;
+        }
+
+        self.cursor = self.cursor.max(end);
+    }
+
+    /// Table rows are always one line, and their cells are found by splitting
+    /// it. Cells comrak added to pad out a short row don't get a span.
+    fn row<'a>(&mut self, node: &'a AstNode<'a>, line: usize) {
+        let line = self.line(line);
+        let line = line.start + container_prefix(node, &self.source[line.clone()])..line.end;
+        let text = &self.source[line.clone()];
+        let end = line.start + text.trim_end().len();
+        let start = line.start + text.len() - text.trim_start().len();
+
+        self.insert(node, start..end.max(start));
+
+        for (cell, range) in node.children().zip(cells(text)) {
+            let range = line.start + range.start..line.start + range.end;
+            self.insert(cell, range.clone());
+
+            self.cursor = range.start;
+            for child in cell.children() {
+                self.inline(child, range.end);
+            }
+        }
+
+        self.cursor = self.cursor.max(end);
+    }
+
+    /// Find `needle` after the cursor (and before `limit`), allowing
+    /// backslash escapes in the source.
+    fn find(&self, needle: &str, limit: usize) -> Option<Range<usize>> {
+        let haystack = &self.source[self.cursor..limit];
+
+        if let Some(found) = haystack.find(needle) {
+            let start = self.cursor + found;
+            return Some(start..start + needle.len());
+        }
+
+        let first = needle.chars().next()?;
+
+        'outer: for (offset, _) in haystack.match_indices([first, '\\']) {
+            let mut source = haystack[offset..].chars().peekable();
+            let mut consumed = 0;
+
+            for expected in needle.chars() {
+                let mut actual = match source.next() {
+                    Some(c) => c,
+                    None => continue 'outer,
+                };
+                consumed += actual.len_utf8();
+
+                if actual == '\\' && expected != '\\' {
+                    actual = match source.next() {
+                        Some(c) => c,
+                        None => continue 'outer,
+                    };
+                    consumed += actual.len_utf8();
+                }
+
+                if actual != expected {
+                    continue 'outer;
+                }
+            }
+
+            let start = self.cursor + offset;
+            return Some(start..start + consumed);
+        }
+
+        None
+    }
+
+    fn delimited<'a>(&mut self, node: &'a AstNode<'a>, limit: usize, delimiter: char, n: usize) {
+        let before = self.cursor;
+        for child in node.children() {
+            self.inline(child, limit);
+        }
+
+        let first = node.first_child().and_then(|c| self.get(c));
+        let last = node.last_child().and_then(|c| self.get(c));
+
+        let (mut start, mut end) = match (first, last) {
+            (Some(f), Some(l)) => (f.start, l.end),
+            _ => return,
+        };
+
+        let bytes = self.source.as_bytes();
+        let delimiter = delimiter as u8;
+
+        for _ in 0..n {
+            if start > before && bytes[start - 1] == delimiter {
+                start -= 1;
+            }
+            if end < limit && bytes[end] == delimiter {
+                end += 1;
+            }
+        }
+
+        self.insert(node, start..end);
+        self.cursor = self.cursor.max(end);
+    }
+
+    fn link<'a>(&mut self, node: &'a AstNode<'a>, limit: usize, image: bool) {
+        let bytes = self.source.as_bytes();
+        let before = self.cursor;
+
+        // Links without text (`[](url)`) have nothing to search for.
+        if node.first_child().is_none() {
+            if let Some(open) = self.find("[", limit) {
+                self.cursor = open.end;
+            }
+        }
+
+        for child in node.children() {
+            self.inline(child, limit);
+        }
+
+        let first = node.first_child().and_then(|c| self.get(c));
+
+        let mut start = match first {
+            Some(f) => f.start,
+            None => self.cursor,
+        };
+
+        let mut autolink = false;
+
+        if start > before && (bytes[start - 1] == b'[' || bytes[start - 1] == b'<') {
+            autolink = bytes[start - 1] == b'<';
+            start -= 1;
+
+            if image && start > before && bytes[start - 1] == b'!' {
+                start -= 1;
+            }
+        }
+
+        let rest = &self.source[self.cursor..limit];
+        let mut end = self.cursor;
+
+        if autolink && rest.starts_with('>') {
+            end += 1;
+        } else if rest.starts_with("](") {
+            // Find the closing parenthesis, accounting for nested ones.
+            let mut depth = 0;
+            for (index, c) in rest.char_indices().skip(2) {
+                match c {
+                    '(' => depth += 1,
+                    ')' if depth == 0 => {
+                        end += index + 1;
+                        break;
+                    }
+                    ')' => depth -= 1,
+                    _ => (),
+                }
+            }
+        } else if let Some(label) = rest.strip_prefix("][") {
+            end += label.find(']').map(|i| i + 3).unwrap_or(1);
+        } else if rest.starts_with(']') {
+            end += 1;
+        }
+
+        self.insert(node, start..end);
+        self.cursor = end;
+    }
+
+    fn inline<'a>(&mut self, node: &'a AstNode<'a>, limit: usize) {
+        let found = match &node.data.borrow().value {
+            NodeValue::Text(text) => {
+                let text = String::from_utf8_lossy(text);
+                self.find(&text, limit)
+            }
+            NodeValue::HtmlInline(html) => {
+                let html = String::from_utf8_lossy(html);
+                self.find(&html, limit)
+            }
+            NodeValue::FootnoteReference(name) => {
+                let name = format!("[^{}]", String::from_utf8_lossy(name));
+                self.find(&name, limit)
+            }
+            NodeValue::Code(code) => {
+                let literal = String::from_utf8_lossy(&code.literal);
+                let bytes = self.source.as_bytes();
+                let before = self.cursor;
+                self.find(&literal, limit).map(|mut range| {
+                    while range.start > before && bytes[range.start - 1] != b'`' {
+                        range.start -= 1;
+                    }
+                    while range.start > before && bytes[range.start - 1] == b'`' {
+                        range.start -= 1;
+                    }
+                    while range.end < limit && bytes[range.end] != b'`' {
+                        range.end += 1;
+                    }
+                    while range.end < limit && bytes[range.end] == b'`' {
+                        range.end += 1;
+                    }
+                    range
+                })
+            }
+            NodeValue::Emph => return self.delimited(node, limit, self.emphasis(limit), 1),
+            NodeValue::Strong => return self.delimited(node, limit, self.emphasis(limit), 2),
+            NodeValue::Strikethrough => return self.delimited(node, limit, '~', 2),
+            NodeValue::Superscript => return self.delimited(node, limit, '^', 1),
+            NodeValue::Link(_) => return self.link(node, limit, false),
+            NodeValue::Image(_) => return self.link(node, limit, true),
+            _ => None,
+        };
+
+        if let Some(range) = found {
+            self.cursor = range.end;
+            self.insert(node, range);
+        }
+    }
+
+    /// Guess which character opens the emphasis starting at the cursor.
+    fn emphasis(&self, limit: usize) -> char {
+        self.source[self.cursor..limit]
+            .chars()
+            .find(|c| *c == '*' || *c == '_')
+            .unwrap_or('*')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use comrak::{Arena, ComrakExtensionOptions, ComrakOptions};
+
+    use super::*;
+
+    fn spans_of(source: &str) -> Vec<(String, String)> {
+        let options = ComrakOptions {
+            extension: ComrakExtensionOptions {
+                table: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let arena = Arena::new();
+        let body = comrak::parse_document(&arena, source, &options);
+        let spans = compute(source, 1, body);
+
+        body.descendants()
+            .filter_map(|node| {
+                let data = node.data.borrow();
+                let span = spans.get(&data)?;
+                let kind = format!("{:?}", data.value);
+                let kind = kind.split(|c: char| !c.is_alphanumeric()).next().unwrap();
+                Some((kind.to_string(), source[span.bytes()].to_string()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn inline_spans() {
+        let spans = spans_of(
+            "## A *b* heading\n\nSee [`xgov-1`](./xgov-1.md \"x\") and\n**bold \\* text**.\n",
+        );
+
+        let expected = [
+            (
+                "Document",
+                "## A *b* heading\n\nSee [`xgov-1`](./xgov-1.md \"x\") and\n**bold \\* text**.",
+            ),
+            ("Heading", "## A *b* heading"),
+            ("Text", "A "),
+            ("Emph", "*b*"),
+            ("Text", "b"),
+            ("Text", " heading"),
+            (
+                "Paragraph",
+                "See [`xgov-1`](./xgov-1.md \"x\") and\n**bold \\* text**.",
+            ),
+            ("Text", "See "),
+            ("Link", "[`xgov-1`](./xgov-1.md \"x\")"),
+            ("Code", "`xgov-1`"),
+            ("Text", " and"),
+            ("Strong", "**bold \\* text**"),
+            ("Text", "bold \\* text"),
+            ("Text", "."),
+        ];
+
+        let spans: Vec<_> = spans
+            .iter()
+            .map(|(k, s)| (k.as_str(), s.as_str()))
+            .collect();
+
+        assert_eq!(spans, expected);
+    }
+
+    #[test]
+    fn table_cells() {
+        let spans = spans_of("| a | b |\n|---|---|\n| b | a |\n");
+
+        let cells: Vec<_> = spans
+            .iter()
+            .filter(|(k, _)| k == "TableCell")
+            .map(|(_, s)| s.as_str())
+            .collect();
+
+        assert_eq!(cells, ["a", "b", "b", "a"]);
+    }
+
+    #[test]
+    fn table_rows() {
+        let spans = spans_of("| a | b |\n|---|---|\n| c |\n|  | \\| |\n");
+
+        let rows: Vec<_> = spans
+            .iter()
+            .filter(|(k, _)| k == "TableRow" || k == "TableCell")
+            .map(|(k, s)| (k.as_str(), s.as_str()))
+            .collect();
+
+        assert_eq!(
+            rows,
+            [
+                ("TableRow", "| a | b |"),
+                ("TableCell", "a"),
+                ("TableCell", "b"),
+                ("TableRow", "| c |"),
+                ("TableCell", "c"),
+                ("TableRow", "|  | \\| |"),
+                ("TableCell", "  "),
+                ("TableCell", "\\|"),
+            ]
+        );
+
+        // Block quote markers aren't part of the row.
+        let quoted = spans_of("> | a | b |\n> |---|---|\n> | c | d |\n");
+
+        let rows: Vec<_> = quoted
+            .iter()
+            .filter(|(k, _)| k == "TableRow" || k == "TableCell")
+            .map(|(k, s)| (k.as_str(), s.as_str()))
+            .collect();
+
+        assert_eq!(
+            rows,
+            [
+                ("TableRow", "| a | b |"),
+                ("TableCell", "a"),
+                ("TableCell", "b"),
+                ("TableRow", "| c | d |"),
+                ("TableCell", "c"),
+                ("TableCell", "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn split_cells() {
+        let line = " a | b\\|c |  ||";
+        let found: Vec<_> = cells(line).into_iter().map(|r| &line[r]).collect();
+        assert_eq!(found, ["a", "b\\|c", "  ", ""]);
+    }
+
+    #[test]
+    fn empty_body_without_newline() {
+        let source = "---\nid: 1\n---";
+        let arena = Arena::new();
+        let body = comrak::parse_document(&arena, "", &ComrakOptions::default());
+        let spans = compute(source, 4, body);
+        assert_eq!(spans.get(&body.data.borrow()), None);
+    }
+
+    #[test]
+    fn position() {
+        let source = "ab\nçd\n";
+        let mut line_starts = vec![0];
+        line_starts.extend(source.match_indices('\n').map(|(i, _)| i + 1));
+        let spans = Spans {
+            line_starts,
+            spans: HashMap::new(),
+        };
+
+        let span = spans.span(source, 3..6);
+        assert_eq!((span.start().line(), span.start().column()), (2, 1));
+        assert_eq!((span.end().line(), span.end().column()), (2, 3));
+
+        let located = spans.source(source, span);
+        assert_eq!(located.line_start, 2);
+        assert_eq!(located.source, "çd");
+        assert_eq!(located.range, (0, 2));
+    }
+}